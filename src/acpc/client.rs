@@ -0,0 +1,167 @@
+use super::state::MatchState;
+use super::token::Token;
+use crate::gameplay::ply::Ply;
+use crate::history::replay::Replay;
+use crate::players::player::Player;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::io::Lines;
+use tokio::net::tcp::OwnedReadHalf;
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::net::TcpStream;
+use tokio::net::ToSocketAddrs;
+
+/// an agent connected to an ACPC dealer.
+///
+/// the dealer sends every MATCHSTATE to both positions, and we answer
+/// only the ones where it is our turn, by echoing the state back
+/// with our Token appended.
+pub struct Client {
+    reader: Lines<BufReader<OwnedReadHalf>>,
+    writer: OwnedWriteHalf,
+}
+
+impl Client {
+    pub const VERSION: &'static str = "VERSION:2.0.0";
+
+    pub async fn connect(addr: impl ToSocketAddrs) -> std::io::Result<Self> {
+        let (reader, writer) = TcpStream::connect(addr).await?.into_split();
+        let mut client = Self {
+            reader: BufReader::new(reader).lines(),
+            writer,
+        };
+        client.send(Self::VERSION).await?;
+        Ok(client)
+    }
+
+    /// play every hand the dealer deals us, until it hangs up.
+    /// the Player begins each hand when its number changes, and
+    /// observes every play it hasn't seen yet before deciding.
+    pub async fn play(mut self, mut player: impl Player) -> std::io::Result<()> {
+        let mut hand = None;
+        let mut seen = 0;
+        while let Some(line) = self.reader.next_line().await? {
+            let line = line.trim();
+            if !line.starts_with("MATCHSTATE:") {
+                continue;
+            }
+            let state = MatchState::try_from(line).map_err(invalid)?;
+            let replay = Replay::try_from(&state).map_err(invalid)?;
+            let game = *replay.games().last().expect("replay starts at a game");
+            if hand != Some(state.hand) {
+                let root = replay.games().first().expect("replay starts at a game");
+                player.begin(&root.seen_by(state.position));
+                hand = Some(state.hand);
+                seen = 0;
+            }
+            for play in replay.hand().plays.iter().skip(seen) {
+                player.observe(play);
+            }
+            seen = replay.hand().plays.len();
+            if game.player() == Ply::Choice(state.position) {
+                let action = player.decide(&game.seen_by(state.position));
+                let token = Token::from((&game, action));
+                self.send(&format!("{}:{}", line, token)).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn send(&mut self, line: &str) -> std::io::Result<()> {
+        self.writer
+            .write_all(format!("{}\r\n", line).as_bytes())
+            .await
+    }
+}
+
+pub(crate) fn invalid(reason: String) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::hand::Hand;
+    use crate::gameplay::action::Action;
+    use crate::gameplay::game::Game;
+    use crate::history::hand::Play;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use tokio::net::TcpListener;
+
+    /// checks whenever it can, and writes down everything it's told
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    impl Player for Recorder {
+        fn decide(&mut self, game: &Game) -> Action {
+            self.0.lock().unwrap().push("decide".to_string());
+            game.passive()
+        }
+        fn begin(&mut self, _: &Game) {
+            self.0.lock().unwrap().push("begin".to_string());
+        }
+        fn observe(&mut self, play: &Play) {
+            let event = format!("observe {}", String::from(play.action));
+            self.0.lock().unwrap().push(event);
+        }
+    }
+
+    #[tokio::test]
+    async fn begins_and_observes_before_deciding() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let dealer = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (reader, mut writer) = stream.into_split();
+            let mut reader = BufReader::new(reader).lines();
+            let mut replies = Vec::new();
+            replies.push(reader.next_line().await.unwrap().unwrap());
+            for (line, answered) in [
+                ("MATCHSTATE:0:0::Ks6h|", false),
+                ("MATCHSTATE:0:0:c:Ks6h|", true),
+                ("MATCHSTATE:0:0:cc/:Ks6h|/8dAs9s", true),
+                ("MATCHSTATE:0:1::Ah2c|", false),
+            ] {
+                writer
+                    .write_all(format!("{}\r\n", line).as_bytes())
+                    .await
+                    .unwrap();
+                if answered {
+                    replies.push(reader.next_line().await.unwrap().unwrap());
+                }
+            }
+            replies
+        });
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let client = Client::connect(addr).await.unwrap();
+        client.play(Recorder(events.clone())).await.unwrap();
+        let replies = dealer.await.unwrap();
+        assert!(
+            replies
+                == vec![
+                    Client::VERSION,
+                    "MATCHSTATE:0:0:c:Ks6h|:c",
+                    "MATCHSTATE:0:0:cc/:Ks6h|/8dAs9s:c",
+                ]
+        );
+        let events = events.lock().unwrap().clone();
+        let flop = format!("observe DEAL {}", Hand::try_from("8d As 9s").unwrap());
+        assert!(
+            events
+                == vec![
+                    "begin",
+                    "observe BLIND 50",
+                    "observe BLIND 100",
+                    "observe CALL 50",
+                    "decide",
+                    "observe CHECK",
+                    flop.as_str(),
+                    "decide",
+                    "begin",
+                    "observe BLIND 50",
+                    "observe BLIND 100",
+                ]
+        );
+    }
+}
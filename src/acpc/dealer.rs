@@ -0,0 +1,169 @@
+use super::client::invalid;
+use super::state::MatchState;
+use super::token::Token;
+use crate::cards::deck::Deck;
+use crate::gameplay::action::Action;
+use crate::gameplay::game::Game;
+use crate::gameplay::ply::Ply;
+use crate::history::hand::HandHistory;
+use crate::Chips;
+use crate::N;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::io::Lines;
+use tokio::net::tcp::OwnedReadHalf;
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::net::TcpListener;
+use tokio::net::ToSocketAddrs;
+
+/// a local ACPC dealer that runs a match between two connected agents.
+///
+/// agents swap positions every hand and stacks are reset every hand,
+/// as in the ACPC heads-up no-limit event. anything an agent says that
+/// the engine would refuse is taken as a call.
+pub struct Dealer {
+    listener: TcpListener,
+}
+
+/// one connected agent
+struct Agent {
+    reader: Lines<BufReader<OwnedReadHalf>>,
+    writer: OwnedWriteHalf,
+}
+
+impl Dealer {
+    pub async fn bind(addr: impl ToSocketAddrs) -> std::io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(addr).await?,
+        })
+    }
+    pub fn local_addr(&self) -> std::io::Result<std::net::SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// accept two agents, play a match, and report
+    /// chips won by each agent in the order they connected
+    pub async fn run(&self, hands: usize) -> std::io::Result<[Chips; N]> {
+        let mut agents = Vec::with_capacity(N);
+        while agents.len() < N {
+            agents.push(self.accept().await?);
+        }
+        let mut totals = [0; N];
+        for hand in 0..hands {
+            let pnls = Self::deal(&mut agents, hand).await?;
+            for (total, pnl) in totals.iter_mut().zip(pnls) {
+                *total += pnl;
+            }
+        }
+        Ok(totals)
+    }
+
+    async fn accept(&self) -> std::io::Result<Agent> {
+        let (stream, _) = self.listener.accept().await?;
+        let (reader, writer) = stream.into_split();
+        let mut agent = Agent {
+            reader: BufReader::new(reader).lines(),
+            writer,
+        };
+        match agent.recv().await? {
+            line if line.starts_with("VERSION:") => Ok(agent),
+            line => Err(invalid(format!("expected version, got: {}", line))),
+        }
+    }
+
+    /// play out one hand, returning each agent's chips won
+    async fn deal(agents: &mut [Agent], hand: usize) -> std::io::Result<[Chips; N]> {
+        let position = |agent: usize| (agent + hand) % N;
+        let mut deck = Deck::new();
+        let seats = [(); N].map(|_| (MatchState::STACK, deck.hole()));
+        let mut game = Game::seated(seats, MatchState::DEALER, MatchState::BLINDS);
+        let mut history = HandHistory::from((hand, &game));
+        loop {
+            let action = match game.player() {
+                Ply::Chance => Action::Draw(game.draw()),
+                Ply::Choice(seat) => {
+                    for (i, agent) in agents.iter_mut().enumerate() {
+                        let state = MatchState::from((position(i), &history));
+                        agent.send(&state.to_string()).await?;
+                    }
+                    let i = (0..N).find(|&i| position(i) == seat).expect("seated");
+                    let state = MatchState::from((seat, &history)).to_string();
+                    let reply = agents[i].recv().await?;
+                    reply
+                        .strip_prefix(state.as_str())
+                        .and_then(|r| r.strip_prefix(':'))
+                        .and_then(|r| Token::try_from(r).ok())
+                        .map(|token| Action::from((&game, token)))
+                        .filter(|action| game.validate(action).is_ok())
+                        .unwrap_or_else(|| Action::from((&game, Token::Call)))
+                }
+                Ply::Terminal => {
+                    history.settle(&game);
+                    for (i, agent) in agents.iter_mut().enumerate() {
+                        let state = MatchState::from((position(i), &history));
+                        agent.send(&state.to_string()).await?;
+                    }
+                    let settlements = game.settlements();
+                    return Ok([(); N]
+                        .iter()
+                        .enumerate()
+                        .map(|(i, _)| settlements[position(i)].pnl())
+                        .collect::<Vec<Chips>>()
+                        .try_into()
+                        .expect("N agents"));
+                }
+            };
+            history.record(&game, action);
+            game = game.apply(action);
+        }
+    }
+}
+
+impl Agent {
+    async fn send(&mut self, line: &str) -> std::io::Result<()> {
+        self.writer
+            .write_all(format!("{}\r\n", line).as_bytes())
+            .await
+    }
+    async fn recv(&mut self) -> std::io::Result<String> {
+        match self.reader.next_line().await? {
+            Some(line) => Ok(line.trim().to_string()),
+            None => Err(std::io::ErrorKind::UnexpectedEof.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::acpc::client::Client;
+
+    #[tokio::test]
+    async fn localhost_match() {
+        let dealer = Dealer::bind("127.0.0.1:0").await.unwrap();
+        let addr = dealer.local_addr().unwrap();
+        let caller = tokio::spawn(async move {
+            let client = Client::connect(addr).await.unwrap();
+            client
+                .play(|game: &Game| Action::from((game, Token::Call)))
+                .await
+        });
+        let raiser = tokio::spawn(async move {
+            let client = Client::connect(addr).await.unwrap();
+            client
+                .play(|game: &Game| {
+                    game.legal()
+                        .into_iter()
+                        .find(|a| matches!(a, Action::Raise(_)))
+                        .unwrap_or(Action::from((game, Token::Call)))
+                })
+                .await
+        });
+        let totals = dealer.run(16).await.unwrap();
+        drop(dealer);
+        assert!(totals.iter().sum::<Chips>() == 0);
+        assert!(caller.await.unwrap().is_ok());
+        assert!(raiser.await.unwrap().is_ok());
+    }
+}
@@ -0,0 +1,4 @@
+pub mod client;
+pub mod dealer;
+pub mod state;
+pub mod token;
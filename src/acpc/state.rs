@@ -0,0 +1,294 @@
+use super::token::Token;
+use crate::cards::board::Board;
+use crate::cards::hand::Hand;
+use crate::cards::hole::Hole;
+use crate::cards::street::Street;
+use crate::gameplay::action::Action;
+use crate::gameplay::game::Game;
+use crate::gameplay::game::Position;
+use crate::history::draft::Draft;
+use crate::history::draft::Raw;
+use crate::history::hand::HandHistory;
+use crate::history::replay::Replay;
+use crate::Chips;
+
+/// a MATCHSTATE message of the ACPC protocol, as seen by one position:
+///
+/// `MATCHSTATE:0:12:r250c/cr500:Ks6h|/8dAs9s/4c`
+///
+/// position 0 is the big blind, position 1 the small blind and button.
+/// hole cards of the other position are only shown at showdown.
+/// heads-up no-limit at the ACPC uses 20000 chip stacks, 50/100 blinds,
+/// and resets stacks every hand, which is what we assume here.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchState {
+    pub position: Position,
+    pub hand: usize,
+    pub rounds: Vec<Vec<Token>>,
+    pub holes: Vec<Option<Hole>>,
+    pub board: Vec<Hand>,
+}
+
+impl MatchState {
+    pub const STACK: Chips = 20_000;
+    pub const BLINDS: (Chips, Chips) = (50, 100);
+    pub const DEALER: Position = 1;
+
+    /// the betting and cards fields, shared with ACPC dealer logs
+    pub fn parse(
+        position: Position,
+        hand: usize,
+        betting: &str,
+        cards: &str,
+    ) -> Result<Self, String> {
+        let rounds = betting
+            .split('/')
+            .map(|mut round| {
+                let mut tokens = Vec::new();
+                while !round.is_empty() {
+                    let (token, rest) = Token::parse(round)?;
+                    tokens.push(token);
+                    round = rest;
+                }
+                Ok(tokens)
+            })
+            .collect::<Result<Vec<Vec<Token>>, String>>()?;
+        let mut cards = cards.split('/');
+        let holes = cards
+            .next()
+            .unwrap_or_default()
+            .split('|')
+            .map(|hole| match Hand::try_from(hole)? {
+                hand if hand.size() == 0 => Ok(None),
+                hand if hand.size() == 2 => Ok(Some(Hole::from(hand))),
+                _ => Err(format!("invalid hole cards: {}", hole)),
+            })
+            .collect::<Result<Vec<Option<Hole>>, String>>()?;
+        let board = cards
+            .map(Hand::try_from)
+            .collect::<Result<Vec<Hand>, String>>()?;
+        Ok(Self {
+            position,
+            hand,
+            rounds,
+            holes,
+            board,
+        })
+    }
+
+    /// the hand so far, in raw ACPC chips
+    pub fn draft(&self) -> Result<Draft, String> {
+        let (small, big) = Self::BLINDS;
+        let mut draft = Draft {
+            id: self.hand,
+            time: 0,
+            dealer: Self::DEALER,
+            blinds: (small as i64, big as i64),
+            stacks: vec![Self::STACK as i64; self.holes.len()],
+            holes: self.holes.clone(),
+            plays: vec![
+                (
+                    Street::Pref,
+                    Some(1),
+                    Raw::Chips(Action::Blind, small as i64),
+                ),
+                (Street::Pref, Some(0), Raw::Chips(Action::Blind, big as i64)),
+            ],
+        };
+        let stack = Self::STACK as i64;
+        let mut spent = [big as i64, small as i64];
+        let mut street = Street::Pref;
+        for round in 0..self.rounds.len().max(self.board.len() + 1) {
+            if round > 0 {
+                let cards = self
+                    .board
+                    .get(round - 1)
+                    .ok_or(format!("betting without cards in: {}", self))?;
+                draft
+                    .plays
+                    .push((street, None, Raw::Exact(Action::Draw(*cards))));
+                street = street.next();
+            }
+            let mut actor = if round == 0 { 1 } else { 0 };
+            for token in self.rounds.get(round).into_iter().flatten() {
+                let raw = match *token {
+                    Token::Fold => Raw::Exact(Action::Fold),
+                    Token::Call => match spent[1 - actor] - spent[actor] {
+                        0 => Raw::Exact(Action::Check),
+                        chips if spent[1 - actor] == stack => Raw::Chips(Action::Shove, chips),
+                        chips => Raw::Chips(Action::Call, chips),
+                    },
+                    Token::Raise(to) => match to as i64 - spent[actor] {
+                        chips if to as i64 == stack => Raw::Chips(Action::Shove, chips),
+                        chips => Raw::Chips(Action::Raise, chips),
+                    },
+                };
+                if let Raw::Chips(_, chips) = raw {
+                    spent[actor] += chips;
+                }
+                draft.plays.push((street, Some(actor), raw));
+                actor = 1 - actor;
+            }
+        }
+        Ok(draft)
+    }
+}
+
+/// the view of a recorded hand from one position. the other hole
+/// is only revealed once the hand is settled at showdown.
+impl From<(Position, &HandHistory)> for MatchState {
+    fn from((position, hand): (Position, &HandHistory)) -> Self {
+        let mut spent = hand.seats.iter().map(|_| 0).collect::<Vec<Chips>>();
+        let mut rounds = vec![Vec::new()];
+        let mut board = Vec::new();
+        for play in hand.plays.iter() {
+            let token = match (play.seat, play.action) {
+                (None, Action::Draw(cards)) => {
+                    board.push(cards);
+                    rounds.push(Vec::new());
+                    continue;
+                }
+                (Some(seat), Action::Blind(chips)) => {
+                    spent[seat] += chips;
+                    continue;
+                }
+                (Some(_), Action::Fold) => Token::Fold,
+                (Some(_), Action::Check) => Token::Call,
+                (Some(seat), Action::Raise(chips) | Action::Shove(chips)) => {
+                    let most = spent.iter().copied().max().unwrap_or(0);
+                    spent[seat] += chips;
+                    if spent[seat] > most {
+                        Token::Raise(spent[seat])
+                    } else {
+                        Token::Call
+                    }
+                }
+                (Some(seat), Action::Call(chips)) => {
+                    spent[seat] += chips;
+                    Token::Call
+                }
+                (_, action) => unreachable!("{} is out of place", action),
+            };
+            rounds.last_mut().expect("at least preflop").push(token);
+        }
+        let reveal = hand.is_complete() && hand.is_showdown();
+        let holes = hand
+            .seats
+            .iter()
+            .map(|e| (e.seat == position || reveal).then_some(e.hole))
+            .collect();
+        Self {
+            position,
+            hand: hand.id,
+            rounds,
+            holes,
+            board,
+        }
+    }
+}
+
+/// the hand so far pushed through the engine, at ACPC scale.
+/// hole cards we cannot see are dealt at random.
+impl TryFrom<&MatchState> for Replay {
+    type Error = String;
+    fn try_from(state: &MatchState) -> Result<Self, Self::Error> {
+        let hand = state.draft()?.exact()?;
+        Replay::try_from(&hand).map_err(|e| e.to_string())
+    }
+}
+
+/// the Game this position is looking at, at ACPC scale.
+/// hole cards we cannot see are dealt at random.
+impl TryFrom<&MatchState> for Game {
+    type Error = String;
+    fn try_from(state: &MatchState) -> Result<Self, Self::Error> {
+        let replay = Replay::try_from(state)?;
+        Ok(*replay.games().last().expect("replay starts at a game"))
+    }
+}
+
+/// str isomorphism
+impl TryFrom<&str> for MatchState {
+    type Error = String;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s.trim().split(':').collect::<Vec<&str>>().as_slice() {
+            ["MATCHSTATE", position, hand, betting, cards] => Self::parse(
+                position
+                    .parse()
+                    .map_err(|_| format!("invalid position: {}", position))?,
+                hand.parse()
+                    .map_err(|_| format!("invalid hand number: {}", hand))?,
+                betting,
+                cards,
+            ),
+            _ => Err(format!("invalid MATCHSTATE: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for MatchState {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let betting = self
+            .rounds
+            .iter()
+            .map(|round| round.iter().map(|t| t.to_string()).collect::<String>())
+            .collect::<Vec<String>>()
+            .join("/");
+        let holes = self
+            .holes
+            .iter()
+            .map(|h| h.map(|h| Self::cards(Hand::from(h))).unwrap_or_default())
+            .collect::<Vec<String>>()
+            .join("|");
+        let board = self
+            .board
+            .iter()
+            .map(|h| format!("/{}", Self::cards(*h)))
+            .collect::<String>();
+        write!(
+            f,
+            "MATCHSTATE:{}:{}:{}:{}{}",
+            self.position, self.hand, betting, holes, board
+        )
+    }
+}
+
+impl MatchState {
+    /// cards run together, as in `Ks6h`
+    fn cards(hand: Hand) -> String {
+        Board::from(hand).to_string().replace(' ', "")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gameplay::ply::Ply;
+
+    #[test]
+    fn bijective_str() {
+        let line = "MATCHSTATE:0:12:r250c/cr500:Ks6h|/8dAs9s";
+        let state = MatchState::try_from(line).unwrap();
+        assert!(state.rounds.len() == 2);
+        assert!(state.holes[1].is_none());
+        assert!(state == MatchState::try_from(state.to_string().as_str()).unwrap());
+    }
+
+    #[test]
+    fn game_at_acpc_scale() {
+        let state = MatchState::try_from("MATCHSTATE:0:12:r250c/cr500:Ks6h|/8dAs9s").unwrap();
+        let game = Game::try_from(&state).unwrap();
+        assert!(game.player() == Ply::Choice(0));
+        assert!(game.to_call() == 250);
+        assert!(game.pot() == 750);
+        assert!(game.seats()[0].cards() == Hole::try_from("Ks 6h").unwrap());
+    }
+
+    #[test]
+    fn view_of_history() {
+        let state = MatchState::try_from("MATCHSTATE:1:3:r300c/:|5dQs/8d9sAs").unwrap();
+        let hand = state.draft().unwrap().exact().unwrap();
+        let view = MatchState::from((1, &hand));
+        assert!(view.to_string() == "MATCHSTATE:1:3:r300c/:|5dQs/8d9sAs");
+    }
+}
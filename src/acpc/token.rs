@@ -0,0 +1,106 @@
+use crate::gameplay::action::Action;
+use crate::gameplay::game::Game;
+use crate::Chips;
+
+/// one betting token of the ACPC protocol.
+///
+/// there is no distinction between checking and calling, or between
+/// raising and shoving. raises are written as the total a player has
+/// put in over the whole hand, so a Token only becomes an Action
+/// relative to the Game it is played in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Token {
+    Fold,
+    Call,
+    Raise(Chips),
+}
+
+impl Token {
+    /// parse the leading token, returning whatever is left over
+    pub fn parse(s: &str) -> Result<(Self, &str), String> {
+        match s.chars().next() {
+            Some('f') => Ok((Self::Fold, &s[1..])),
+            Some('c') => Ok((Self::Call, &s[1..])),
+            Some('r') => {
+                let digits = s[1..].chars().take_while(|c| c.is_ascii_digit()).count();
+                let to = s[1..1 + digits]
+                    .parse::<Chips>()
+                    .map_err(|_| format!("invalid raise: {}", s))?;
+                Ok((Self::Raise(to), &s[1 + digits..]))
+            }
+            _ => Err(format!("invalid token: {}", s)),
+        }
+    }
+}
+
+/// what the actor of this Game means by an Action
+impl From<(&Game, Action)> for Token {
+    fn from((game, action): (&Game, Action)) -> Self {
+        match action {
+            Action::Fold => Self::Fold,
+            Action::Check | Action::Call(_) => Self::Call,
+            Action::Raise(chips) | Action::Shove(chips) if chips > game.to_call() => {
+                Self::Raise(game.actor().spent() + chips)
+            }
+            Action::Raise(_) | Action::Shove(_) => Self::Call,
+            Action::Blind(_) | Action::Draw(_) => unreachable!("not a decision"),
+        }
+    }
+}
+
+/// what a Token means to the actor of this Game
+impl From<(&Game, Token)> for Action {
+    fn from((game, token): (&Game, Token)) -> Self {
+        match token {
+            Token::Fold => Action::Fold,
+            Token::Call if game.to_call() == 0 => Action::Check,
+            Token::Call if game.to_call() >= game.to_shove() => Action::Shove(game.to_shove()),
+            Token::Call => Action::Call(game.to_call()),
+            Token::Raise(to) => match to - game.actor().spent() {
+                chips if chips >= game.to_shove() => Action::Shove(game.to_shove()),
+                chips => Action::Raise(chips),
+            },
+        }
+    }
+}
+
+/// str isomorphism
+impl TryFrom<&str> for Token {
+    type Error = String;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match Self::parse(s.trim())? {
+            (token, "") => Ok(token),
+            (_, rest) => Err(format!("trailing characters: {}", rest)),
+        }
+    }
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Fold => write!(f, "f"),
+            Self::Call => write!(f, "c"),
+            Self::Raise(to) => write!(f, "r{}", to),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bijective_str() {
+        for token in [Token::Fold, Token::Call, Token::Raise(250)] {
+            assert!(token == Token::try_from(token.to_string().as_str()).unwrap());
+        }
+    }
+
+    #[test]
+    fn bijective_action() {
+        let game = Game::root();
+        for action in game.legal() {
+            assert!(action == Action::from((&game, Token::from((&game, action)))));
+        }
+    }
+}
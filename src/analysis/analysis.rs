@@ -240,6 +240,7 @@ impl Analysis {
             CREATE TABLE IF NOT EXISTS metric      (xor  BIGINT, dx   REAL);
             CREATE TABLE IF NOT EXISTS abstraction (abs  BIGINT, st   SMALLINT);
             CREATE TABLE IF NOT EXISTS blueprint   (edge BIGINT, past BIGINT, present BIGINT, future BIGINT, policy REAL, regret REAL);
+            ALTER TABLE blueprint ADD COLUMN IF NOT EXISTS prediction REAL;
         "#).await?)
     }
     async fn truncate(&self) -> Result<(), E> {
@@ -264,7 +265,7 @@ impl Analysis {
             .to_string_lossy()
             .into_owned();
         Ok(self.0.batch_execute(format!(r#"                                                                                  
-            COPY blueprint (past, present, future, edge, policy, regret) FROM '{}/blueprint.profile.pgcopy' WITH (FORMAT BINARY);
+            COPY blueprint (past, present, future, edge, policy, regret, prediction) FROM '{}/blueprint.profile.pgcopy' WITH (FORMAT BINARY);
             CREATE INDEX IF NOT EXISTS idx_blueprint_bucket  ON blueprint (present, past, future);
             CREATE INDEX IF NOT EXISTS idx_blueprint_future  ON blueprint (future);
             CREATE INDEX IF NOT EXISTS idx_blueprint_present ON blueprint (present);
@@ -2,7 +2,7 @@ use super::card::Card;
 use super::hand::Hand;
 use super::street::Street;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Board(Hand);
 
 impl Board {
@@ -46,6 +46,19 @@ impl From<Board> for Hand {
         board.0
     }
 }
+/// str isomorphism
+/// this follows from Hand isomorphism
+impl TryFrom<&str> for Board {
+    type Error = String;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let hand = Hand::try_from(s)?;
+        match hand.size() {
+            0 | 3 | 4 | 5 => Ok(Self(hand)),
+            _ => Err("Board must contain 0, 3, 4, or 5 cards".into()),
+        }
+    }
+}
+
 impl std::fmt::Display for Board {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(
@@ -59,3 +72,15 @@ impl std::fmt::Display for Board {
         )
     }
 }
+
+impl serde::Serialize for Board {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(self.to_string().as_str())
+    }
+}
+impl<'de> serde::Deserialize<'de> for Board {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(d)?;
+        Self::try_from(s.as_str()).map_err(serde::de::Error::custom)
+    }
+}
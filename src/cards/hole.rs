@@ -55,3 +55,15 @@ impl TryFrom<&str> for Hole {
         }
     }
 }
+
+impl serde::Serialize for Hole {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(self.to_string().as_str())
+    }
+}
+impl<'de> serde::Deserialize<'de> for Hole {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(d)?;
+        Self::try_from(s.as_str()).map_err(serde::de::Error::custom)
+    }
+}
@@ -14,6 +14,12 @@ pub struct Strength {
     kicks: Kickers,
 }
 
+impl Strength {
+    pub fn ranking(&self) -> Ranking {
+        self.value
+    }
+}
+
 impl From<Hand> for Strength {
     fn from(hand: Hand) -> Self {
         Self::from(Evaluator::from(hand))
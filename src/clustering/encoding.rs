@@ -16,16 +16,24 @@ use std::collections::BTreeMap;
 pub struct Encoder(BTreeMap<Isomorphism, Abstraction>);
 
 impl Encoder {
-    /// only run this once.
+    /// only run this once. if we're interrupted, the street
+    /// we were clustering is thrown away rather than saved half
+    /// learned, and we start over next time, since done() wants
+    /// all of them.
     pub fn learn() {
         if Self::done() {
             log::info!("skipping abstraction");
         } else {
             log::info!("learning abstraction");
-            Layer::outer()
-                .inner() // turn
-                .inner() // flop
-                .inner(); // preflop
+            let turn = Layer::outer().inner();
+            if crate::interrupted() {
+                return log::warn!("stopping abstraction before the flop");
+            }
+            let flop = turn.inner();
+            if crate::interrupted() {
+                return log::warn!("stopping abstraction before the preflop");
+            }
+            flop.inner();
         }
     }
     /// simple insertion.
@@ -94,7 +102,7 @@ impl Encoder {
         Street::all()
             .iter()
             .map(|street| format!("{}.encoder.pgcopy", street))
-            .any(|file| std::fs::metadata(file).is_ok())
+            .all(|file| std::fs::metadata(file).is_ok())
     }
     pub fn load() -> Self {
         log::info!("loading encoder");
@@ -139,7 +147,9 @@ impl Encoder {
         use byteorder::BE;
         use std::fs::File;
         use std::io::Write;
-        let ref mut file = File::create(format!("{}.encoder.pgcopy", street)).expect("touch");
+        let path = format!("{}.encoder.pgcopy", street);
+        let temp = format!("{}.tmp", path);
+        let file = &mut File::create(&temp).expect("touch");
         file.write_all(b"PGCOPY\n\xFF\r\n\0").expect("header");
         file.write_u32::<BE>(0).expect("flags");
         file.write_u32::<BE>(0).expect("extension");
@@ -152,6 +162,8 @@ impl Encoder {
             file.write_i64::<BE>(i64::from(*abs)).unwrap();
         }
         file.write_u16::<BE>(0xFFFF).expect("trailer");
+        file.sync_all().expect("flush encoding");
+        std::fs::rename(temp, path).expect("rename encoding");
     }
 }
 
@@ -78,6 +78,10 @@ impl Layer {
         // Layer::save() -> Self
         layer.kmeans_initial();
         layer.kmeans_cluster();
+        if crate::interrupted() {
+            log::warn!("discarding unfinished {} clustering", layer.street);
+            return layer;
+        }
         layer.metric.save(layer.street);
         layer.encode.save(layer.street);
         layer
@@ -187,6 +191,9 @@ impl Layer {
     /// for however many iterations we want,
     /// 1. assign each `Observation` to the nearest `Centroid`
     /// 2. update each `Centroid` by averaging the `Observation`s assigned to it
+    ///
+    /// if we're interrupted, we stop after the current iteration.
+    /// the clustering is unfinished then, so inner() won't save it.
     fn kmeans_cluster(&mut self) {
         let t = Self::t(self.street);
         log::info!(
@@ -205,11 +212,20 @@ impl Layer {
         // SLOP SLOP SLOP
         // SLOP SLOP SLOP
         let progress = crate::progress(t);
-        for _ in 0..t {
+        for i in 0..t {
             let neighbors = self.get_neighbor();
             self.set_neighbor(neighbors);
             self.set_orphaned();
             progress.inc(1);
+            if crate::interrupted() {
+                log::warn!(
+                    "stopping {} clustering after {} of {} iterations",
+                    self.street,
+                    i + 1,
+                    t
+                );
+                break;
+            }
         }
         progress.finish();
     }
@@ -36,10 +36,10 @@ impl Metric {
     }
 
     pub fn done() -> bool {
-        Street::all()
+        [Street::Pref, Street::Flop, Street::Turn]
             .iter()
             .map(|street| format!("{}.metric.pgcopy", street))
-            .any(|file| std::fs::metadata(file).is_ok())
+            .all(|file| std::fs::metadata(file).is_ok())
     }
     pub fn load() -> Self {
         log::info!("loading metric");
@@ -83,7 +83,9 @@ impl Metric {
         use byteorder::BE;
         use std::fs::File;
         use std::io::Write;
-        let ref mut file = File::create(format!("{}.metric.pgcopy", street)).expect("touch");
+        let path = format!("{}.metric.pgcopy", street);
+        let temp = format!("{}.tmp", path);
+        let file = &mut File::create(&temp).expect("touch");
         file.write_all(b"PGCOPY\n\xFF\r\n\0").expect("header");
         file.write_u32::<BE>(0).expect("flags");
         file.write_u32::<BE>(0).expect("extension");
@@ -96,6 +98,8 @@ impl Metric {
             file.write_f32::<BE>(*distance).unwrap();
         }
         file.write_u16::<BE>(0xFFFF).expect("trailer");
+        file.sync_all().expect("flush metric");
+        std::fs::rename(temp, path).expect("rename metric");
     }
 }
 
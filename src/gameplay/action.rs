@@ -3,6 +3,8 @@ use crate::cards::hand::Hand;
 use crate::Chips;
 use colored::*;
 
+/// the lowest 8 bits hold the kind of Action, the other 24 its Chips,
+/// so amounts round trip up to 0xFF_FFFF
 const MASK: u32 = 0xFF;
 const BITS: u32 = MASK.count_ones();
 
@@ -102,6 +104,54 @@ impl std::fmt::Display for Action {
     }
 }
 
+/// str isomorphism
+/// plain, uncolored tokens for stable serialization
+/// FOLD | CHECK | CALL 2 | RAISE 6 | SHOVE 98 | BLIND 1 | DEAL 7h8h9c
+impl TryFrom<&str> for Action {
+    type Error = String;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let mut tokens = s.split_whitespace();
+        let kind = tokens.next().ok_or("empty action")?.to_uppercase();
+        let data = tokens.collect::<Vec<&str>>().join(" ");
+        let bets = || data.parse::<Chips>().map_err(|e| format!("{}: {}", s, e));
+        match kind.as_str() {
+            "FOLD" => Ok(Action::Fold),
+            "CHECK" => Ok(Action::Check),
+            "CALL" => Ok(Action::Call(bets()?)),
+            "RAISE" => Ok(Action::Raise(bets()?)),
+            "SHOVE" => Ok(Action::Shove(bets()?)),
+            "BLIND" => Ok(Action::Blind(bets()?)),
+            "DEAL" => Ok(Action::Draw(Hand::try_from(data.as_str())?)),
+            _ => Err(format!("unknown action: {}", s)),
+        }
+    }
+}
+impl From<Action> for String {
+    fn from(action: Action) -> Self {
+        match action {
+            Action::Draw(hand) => format!("DEAL {}", hand),
+            Action::Check => "CHECK".to_string(),
+            Action::Fold => "FOLD".to_string(),
+            Action::Blind(amount) => format!("BLIND {}", amount),
+            Action::Call(amount) => format!("CALL {}", amount),
+            Action::Raise(amount) => format!("RAISE {}", amount),
+            Action::Shove(amount) => format!("SHOVE {}", amount),
+        }
+    }
+}
+
+impl serde::Serialize for Action {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(String::from(*self).as_str())
+    }
+}
+impl<'de> serde::Deserialize<'de> for Action {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(d)?;
+        Self::try_from(s.as_str()).map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -113,9 +163,26 @@ mod tests {
             Action::Blind(2),
             Action::Call(32767),
             Action::Shove(1738),
+            Action::Raise(20_000),
+            Action::Shove(0xFF_FFFF),
             Action::Draw(Hand::try_from("2c Th As").unwrap()),
         ] {
             assert!(action == Action::from(u32::from(action)));
         }
     }
+
+    #[test]
+    fn bijective_str() {
+        for action in [
+            Action::Fold,
+            Action::Check,
+            Action::Call(32767),
+            Action::Raise(6),
+            Action::Shove(1738),
+            Action::Blind(2),
+            Action::Draw(Hand::try_from("2c Th As").unwrap()),
+        ] {
+            assert!(action == Action::try_from(String::from(action).as_str()).unwrap());
+        }
+    }
 }
@@ -0,0 +1,332 @@
+use super::game::Game;
+use super::game::Position;
+use super::table::Table;
+use crate::cards::deck::Deck;
+use crate::cards::hand::Hand;
+use crate::cards::hole::Hole;
+use crate::cards::street::Street;
+use crate::history::replay::Replay;
+use crate::players::player::Player;
+use crate::Chips;
+use crate::N;
+use rayon::iter::IntoParallelIterator;
+use rayon::iter::ParallelIterator;
+
+/// builds a fresh Player for every deal, so that deals can run in parallel
+pub type Entrant = Box<dyn Fn() -> Box<dyn Player> + Send + Sync>;
+
+/// heads-up matches between every pair of entrants.
+///
+/// each deal is played in duplicate: the same hole cards and board are
+/// dealt twice, with the players swapping seats in between. whatever luck
+/// one player had in the first hand, the other has in the second, so most
+/// of the card variance cancels out of the pair. stacks start fresh every
+/// hand, and deals are spread across threads with rayon.
+pub struct Arena {
+    entrants: Vec<(String, Entrant)>,
+    blinds: (Chips, Chips),
+}
+
+impl Default for Arena {
+    fn default() -> Self {
+        Self {
+            entrants: Vec::new(),
+            blinds: (crate::S_BLIND, crate::B_BLIND),
+        }
+    }
+}
+
+impl Arena {
+    pub fn enter<F>(&mut self, name: &str, entrant: F)
+    where
+        F: Fn() -> Box<dyn Player> + Send + Sync + 'static,
+    {
+        self.entrants.push((name.to_string(), Box::new(entrant)));
+    }
+    pub fn set_blinds(&mut self, blinds: (Chips, Chips)) {
+        self.blinds = blinds;
+    }
+
+    /// play this many duplicate deals between every pair of entrants
+    pub fn run(&self, deals: usize) -> Vec<Matchup> {
+        let n = self.entrants.len();
+        (0..n)
+            .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+            .map(|(i, j)| self.matchup(i, j, deals))
+            .inspect(|m| log::info!("{}", m))
+            .collect()
+    }
+
+    fn matchup(&self, hero: usize, villain: usize, deals: usize) -> Matchup {
+        let stats = (0..deals)
+            .into_par_iter()
+            .map(|id| self.duplicate(hero, villain, id))
+            .reduce(Stats::default, |a, b| a + b);
+        Matchup {
+            hero: self.entrants[hero].0.clone(),
+            villain: self.entrants[villain].0.clone(),
+            bblind: self.blinds.1,
+            stats,
+        }
+    }
+
+    /// one deal, played from both sides. the hero holds the
+    /// button's cards in the first hand and the big blind's in the second.
+    fn duplicate(&self, hero: usize, villain: usize, id: usize) -> Stats {
+        let mut deck = Deck::new();
+        let holes = [(); N].map(|_| deck.hole());
+        let runout = [Street::Pref, Street::Flop, Street::Turn].map(|s| deck.deal(s));
+        let mut stats = Stats::default();
+        let mut total = 0;
+        let mut adjusted = 0.;
+        for (seat, order) in [(0, [hero, villain]), (1, [villain, hero])] {
+            let replay = self.hand(order, holes, runout, 2 * id + seat);
+            adjusted += replay.adjusted()[seat] as f64;
+            let hand = replay.hand();
+            let pnl = hand.settlements()[seat].pnl();
+            let tally = Tally::from(pnl);
+            stats.positions[(seat + N - hand.dealer) % N] += tally;
+            if hand.is_showdown() {
+                stats.showdown += tally;
+            } else {
+                stats.folded += tally;
+            }
+            total += pnl;
+        }
+        stats.duplicate += Tally::from(total);
+        stats.adjusted += Tally::from(adjusted);
+        stats
+    }
+
+    fn hand(&self, order: [usize; N], holes: [Hole; N], runout: [Hand; 3], id: usize) -> Replay {
+        let mut players = order.map(|i| (self.entrants[i].1)());
+        let seats = holes.map(|hole| (crate::STACK, hole));
+        let game = Game::seated(seats, 0, self.blinds);
+        Table::record(&mut players, &mut [], game, id, |game| {
+            runout[game.board().street() as usize]
+        })
+    }
+}
+
+/// what one entrant won off another, from the hero's side
+pub struct Matchup {
+    pub hero: String,
+    pub villain: String,
+    pub bblind: Chips,
+    pub stats: Stats,
+}
+
+impl Matchup {
+    fn bb100(&self, chips: f64) -> f64 {
+        chips / self.bblind as f64 * 100.
+    }
+    /// hands played, counting both sides of every deal
+    pub fn hands(&self) -> usize {
+        self.stats.duplicate.n() * 2
+    }
+    /// win rate and its standard error, in big blinds per 100 hands.
+    /// the duplicate pair is our unit of observation, since its two
+    /// hands share cards and are anything but independent.
+    pub fn winrate(&self) -> (f64, f64) {
+        let pair = self.stats.duplicate;
+        (self.bb100(pair.mean() / 2.), self.bb100(pair.error() / 2.))
+    }
+    /// win rate and its standard error in bb/100, all-in adjusted:
+    /// hands that were all-in before the river count at their equity
+    pub fn adjusted(&self) -> (f64, f64) {
+        let pair = self.stats.adjusted;
+        (self.bb100(pair.mean() / 2.), self.bb100(pair.error() / 2.))
+    }
+    /// win rate and standard error in bb/100 from one position,
+    /// where 0 is the button and 1 is the big blind
+    pub fn position(&self, position: Position) -> (f64, f64) {
+        let tally = self.stats.positions[position];
+        (self.bb100(tally.mean()), self.bb100(tally.error()))
+    }
+    /// bb/100 won in hands that went to showdown, and in hands
+    /// that did not, over all hands played. they sum to the win rate.
+    pub fn showdown(&self) -> (f64, f64) {
+        let hands = self.hands().max(1) as f64;
+        (
+            self.bb100(self.stats.showdown.sum() / hands),
+            self.bb100(self.stats.folded.sum() / hands),
+        )
+    }
+}
+
+/// everything we keep track of over a matchup. all of it
+/// adds up across deals, which is how threads share their work.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Stats {
+    pub duplicate: Tally,
+    pub adjusted: Tally,
+    pub positions: [Tally; N],
+    pub showdown: Tally,
+    pub folded: Tally,
+}
+
+impl std::ops::Add for Stats {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        let mut positions = self.positions;
+        for (a, b) in positions.iter_mut().zip(other.positions) {
+            *a += b;
+        }
+        Self {
+            duplicate: self.duplicate + other.duplicate,
+            adjusted: self.adjusted + other.adjusted,
+            positions,
+            showdown: self.showdown + other.showdown,
+            folded: self.folded + other.folded,
+        }
+    }
+}
+
+/// running count, sum, and sum of squares of chips won.
+/// kept in f64 so that millions of hands don't lose precision.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Tally {
+    n: usize,
+    sum: f64,
+    squares: f64,
+}
+
+impl Tally {
+    pub fn n(&self) -> usize {
+        self.n
+    }
+    pub fn sum(&self) -> f64 {
+        self.sum
+    }
+    pub fn mean(&self) -> f64 {
+        if self.n == 0 {
+            0.
+        } else {
+            self.sum / self.n as f64
+        }
+    }
+    /// sample variance, with Bessel's correction
+    pub fn variance(&self) -> f64 {
+        if self.n < 2 {
+            0.
+        } else {
+            let n = self.n as f64;
+            ((self.squares - self.sum * self.sum / n) / (n - 1.)).max(0.)
+        }
+    }
+    /// standard error of the mean
+    pub fn error(&self) -> f64 {
+        if self.n == 0 {
+            0.
+        } else {
+            (self.variance() / self.n as f64).sqrt()
+        }
+    }
+}
+
+impl From<Chips> for Tally {
+    fn from(chips: Chips) -> Self {
+        Self::from(chips as f64)
+    }
+}
+
+impl From<f64> for Tally {
+    fn from(x: f64) -> Self {
+        Self {
+            n: 1,
+            sum: x,
+            squares: x * x,
+        }
+    }
+}
+
+impl std::ops::Add for Tally {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Self {
+            n: self.n + other.n,
+            sum: self.sum + other.sum,
+            squares: self.squares + other.squares,
+        }
+    }
+}
+
+impl std::ops::AddAssign for Tally {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl std::fmt::Display for Matchup {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let (rate, error) = self.winrate();
+        let (adjusted, adjusted_error) = self.adjusted();
+        let (button, button_error) = self.position(0);
+        let (blind, blind_error) = self.position(1);
+        let (showdown, folded) = self.showdown();
+        writeln!(
+            f,
+            "{} vs {} over {} hands",
+            self.hero,
+            self.villain,
+            self.hands()
+        )?;
+        writeln!(f, "  bb/100    {:>+9.2} ± {:.2}", rate, error)?;
+        writeln!(f, "  all-in ev {:>+9.2} ± {:.2}", adjusted, adjusted_error)?;
+        writeln!(f, "  button    {:>+9.2} ± {:.2}", button, button_error)?;
+        writeln!(f, "  big blind {:>+9.2} ± {:.2}", blind, blind_error)?;
+        write!(
+            f,
+            "  showdown  {:>+9.2}   non-showdown {:>+9.2}",
+            showdown, folded
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::players::caller::Caller;
+    use crate::players::random::Random;
+
+    #[test]
+    fn mirrored_stations_break_even() {
+        let mut arena = Arena::default();
+        arena.enter("hero", || Box::new(Caller));
+        arena.enter("villain", || Box::new(Caller));
+        let matchups = arena.run(100);
+        assert!(matchups.len() == 1);
+        assert!(matchups[0].hands() == 200);
+        assert!(matchups[0].winrate() == (0., 0.));
+        assert!(matchups[0].adjusted() == (0., 0.));
+    }
+
+    #[test]
+    fn round_robin_adds_up() {
+        let mut arena = Arena::default();
+        arena.enter("a", || Box::new(Random));
+        arena.enter("b", || Box::new(Random));
+        arena.enter("c", || Box::new(Caller));
+        let matchups = arena.run(50);
+        assert!(matchups.len() == 3);
+        for matchup in matchups.iter() {
+            let stats = matchup.stats;
+            let sides = stats.positions[0].sum() + stats.positions[1].sum();
+            let split = stats.showdown.sum() + stats.folded.sum();
+            assert!(stats.duplicate.sum() == sides);
+            assert!(stats.duplicate.sum() == split);
+            assert!(stats.positions.iter().all(|t| t.n() == 50));
+        }
+    }
+
+    #[test]
+    fn tally_error() {
+        let tally = [1, -1, 1, -1]
+            .map(Tally::from)
+            .into_iter()
+            .fold(Tally::default(), |a, b| a + b);
+        assert!(tally.mean() == 0.);
+        assert!(tally.variance() == 4. / 3.);
+        assert!(tally.error() == (1. / 3f64).sqrt());
+    }
+}
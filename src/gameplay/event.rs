@@ -0,0 +1,82 @@
+use super::action::Action;
+use super::game::Position;
+use super::pot::Pot;
+use crate::cards::board::Board;
+use crate::cards::hole::Hole;
+use crate::cards::street::Street;
+use crate::history::hand::HandHistory;
+use crate::Chips;
+
+/// something that happened at a Table, in the order it happened.
+///
+/// a hand opens with Started, then one Dealt per seat, then the blinds
+/// as Acted. betting and board cards follow in turn, and the hand closes
+/// with Showdown (if anyone turned cards over), Awarded, and Finished.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// a new hand, with each seat's stack before the blinds
+    Started {
+        hand: usize,
+        dealer: Position,
+        blinds: (Chips, Chips),
+        stacks: Vec<Chips>,
+    },
+    /// hole cards, for the seat that holds them and nobody else
+    Dealt { seat: Position, hole: Hole },
+    /// somebody acted, blinds included
+    Acted { seat: Position, action: Action },
+    /// board cards fell, and betting moves on to this Street
+    Advanced { street: Street, board: Board },
+    /// the holes turned over between seats that didn't fold
+    Showdown { shown: Vec<(Position, Hole)> },
+    /// main and side pots, with the seats that won each one
+    Awarded { pots: Vec<Pot> },
+    /// the complete record of the hand that just ended
+    Finished(HandHistory),
+}
+
+impl Event {
+    /// may this seat see it? None sees everything, as the dealer would
+    pub fn is_visible(&self, seat: Option<Position>) -> bool {
+        match (self, seat) {
+            (Event::Dealt { seat: dealt, .. }, Some(seat)) => *dealt == seat,
+            (Event::Finished(_), Some(_)) => false,
+            _ => true,
+        }
+    }
+}
+
+impl std::fmt::Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Event::Started {
+                hand,
+                dealer,
+                blinds,
+                stacks,
+            } => write!(
+                f,
+                "HAND #{} {}/{} BTN P{} STACKS {:?}",
+                hand, blinds.0, blinds.1, dealer, stacks
+            ),
+            Event::Dealt { seat, hole } => write!(f, "P{} DEALT {}", seat, hole),
+            Event::Acted { seat, action } => write!(f, "P{} {}", seat, action),
+            Event::Advanced { street, board } => write!(f, "{} {}", street, board),
+            Event::Showdown { shown } => {
+                write!(f, "SHOWDOWN")?;
+                for (seat, hole) in shown {
+                    write!(f, " P{} {}", seat, hole)?;
+                }
+                Ok(())
+            }
+            Event::Awarded { pots } => {
+                write!(f, "AWARDED")?;
+                for pot in pots {
+                    write!(f, " {}", pot)?;
+                }
+                Ok(())
+            }
+            Event::Finished(hand) => write!(f, "FINISHED #{}", hand.id),
+        }
+    }
+}
@@ -1,34 +1,52 @@
 use super::action::Action;
+use super::pot::Pot;
+use super::rake::Rake;
 use super::seat::Seat;
 use super::seat::State;
 use super::settlement::Settlement;
 use crate::cards::board::Board;
+use crate::cards::card::Card;
 use crate::cards::deck::Deck;
 use crate::cards::hand::Hand;
+use crate::cards::hands::HandIterator;
+use crate::cards::hole::Hole;
 use crate::cards::observation::Observation;
 use crate::cards::street::Street;
 use crate::cards::strength::Strength;
 use crate::gameplay::ply::Ply;
 use crate::gameplay::showdown::Showdown;
-use crate::players::human::Human;
 use crate::Chips;
+use crate::Utility;
 use crate::N;
 use crate::STACK;
+use rayon::iter::IntoParallelIterator;
+use rayon::iter::ParallelIterator;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::OnceLock;
 
-type Position = usize;
+/// runouts counted by how the live hands rank against each other,
+/// each with one runout that ranks them that way
+type Rankings = BTreeMap<[usize; N], (Hand, usize)>;
+
+pub type Position = usize;
 /// Rotation represents the memoryless state of the game in between actions.
 ///
 /// It records both public and private data structs, and is responsible for managing the
 /// rotation of players, the pot, and the board. Its immutable methods reveal
 /// pure functions representing the rules of how the game may proceed.
 /// This full game state will also be our CFR node representation.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct Game {
     seats: [Seat; N],
     pot: Chips,
     board: Board,
     dealer: Position,
     ticker: Position,
+    sblind: Chips,
+    bblind: Chips,
+    rake: Rake,
 }
 
 impl Game {
@@ -38,18 +56,32 @@ impl Game {
     /// these should not matter too much in the MCCFR algorithm,
     /// as long as we alternate the traverser/paths explored
     pub fn root() -> Self {
-        let mut root = Self {
+        let mut deck = Deck::new();
+        let seats = [(); N].map(|_| (STACK, deck.hole()));
+        Self::seated(seats, 0, (crate::S_BLIND, crate::B_BLIND))
+    }
+    /// start a hand at its first decision from known stacks and hole cards,
+    /// with the button at dealer and (small, big) blinds already posted.
+    /// this is how we pick up hands that were dealt somewhere else.
+    pub fn seated(seats: [(Chips, Hole); N], dealer: Position, blinds: (Chips, Chips)) -> Self {
+        let mut game = Self {
             pot: 0 as Chips,
-            dealer: 0usize,
+            dealer,
             ticker: 0usize,
             board: Board::empty(),
-            seats: [Seat::from(STACK); N],
+            seats: seats.map(|(stack, hole)| {
+                let mut seat = Seat::from(stack);
+                seat.reset_cards(hole);
+                seat
+            }),
+            sblind: blinds.0,
+            bblind: blinds.1,
+            rake: Rake::default(),
         };
-        root.next_player();
-        root.deal_cards();
-        root.post_blinds(Self::sblind());
-        root.post_blinds(Self::bblind());
-        root
+        game.next_player();
+        game.post_blinds(game.sblind);
+        game.post_blinds(game.bblind);
+        game
     }
     pub fn n(&self) -> usize {
         self.seats.len()
@@ -59,20 +91,23 @@ impl Game {
         child.act(action);
         child
     }
-    pub fn play() -> ! {
-        let mut node = Self::root();
-        loop {
-            match node.player() {
-                Ply::Chance => todo!(), // node.show_revealed(),
-                Ply::Choice(_) => {
-                    node.act(Human::decide(&node));
-                }
-                Ply::Terminal => {
-                    node.conclude();
-                    node.commence();
-                }
+    /// the same Game as seen from one seat, with every other hole hidden
+    pub fn seen_by(&self, seat: Position) -> Self {
+        let mut game = *self;
+        for (i, s) in game.seats.iter_mut().enumerate() {
+            if i != seat {
+                s.reset_cards(Hole::empty());
             }
         }
+        game
+    }
+
+    /// the same Game with one seat holding other cards,
+    /// for asking how a hand we can't see would be played
+    pub fn holding(&self, seat: Position, hole: Hole) -> Self {
+        let mut game = *self;
+        game.seats[seat].reset_cards(hole);
+        game
     }
 
     //
@@ -82,6 +117,17 @@ impl Game {
     pub fn board(&self) -> Board {
         self.board
     }
+    pub fn dealer(&self) -> Position {
+        self.dealer
+    }
+    pub fn seats(&self) -> &[Seat] {
+        &self.seats
+    }
+    /// the seats that post the small and big blinds, in that order
+    pub fn blinders(&self) -> (Position, Position) {
+        let shift = self.dealer + Self::offset(Street::Pref);
+        ((shift + 1) % N, (shift + 2) % N)
+    }
     pub fn player(&self) -> Ply {
         if self.is_terminal() {
             Ply::Terminal
@@ -104,7 +150,11 @@ impl Game {
             return options;
         }
         if self.is_blinding() {
-            options.push(Action::Blind(Self::sblind()));
+            if self.next_blind() < self.to_shove() {
+                options.push(Action::Blind(self.next_blind()));
+            } else {
+                options.push(Action::Shove(self.to_shove()));
+            }
             return options;
         }
         if self.can_raise() {
@@ -125,54 +175,6 @@ impl Game {
         options
     }
 
-    //
-    fn conclude(&mut self) {
-        self.give_chips();
-    }
-    fn commence(&mut self) {
-        assert!(self.seats.iter().all(|s| s.stack() > 0), "game over");
-        self.wipe_board();
-        self.deal_cards();
-        self.move_button();
-        self.post_blinds(Self::sblind());
-        self.post_blinds(Self::bblind());
-    }
-    fn give_chips(&mut self) {
-        log::trace!("::::::::::::::");
-        log::trace!("{}", self.board());
-        for (_, (settlement, seat)) in self
-            .settlements()
-            .iter()
-            .zip(self.seats.iter_mut())
-            .enumerate()
-            .inspect(|(i, (x, s))| log::trace!("{} {} {:>7} {}", i, s.cards(), s.stack(), x.pnl()))
-        {
-            seat.win(settlement.reward);
-        }
-    }
-    fn wipe_board(&mut self) {
-        self.pot = 0;
-        self.board.clear();
-        assert!(self.board.street() == Street::Pref);
-    }
-    fn deal_cards(&mut self) {
-        assert!(self.board.street() == Street::Pref);
-        let mut deck = Deck::new();
-        for seat in self.seats.iter_mut() {
-            seat.reset_state(State::Betting);
-            seat.reset_cards(deck.hole());
-            seat.reset_stake();
-            seat.reset_spent();
-        }
-    }
-    fn move_button(&mut self) {
-        assert!(self.seats.len() == N);
-        assert!(self.board.street() == Street::Pref);
-        self.dealer += 1;
-        self.dealer %= N;
-        self.ticker = self.dealer;
-        self.next_player();
-    }
     fn post_blinds(&mut self, blind: Chips) {
         assert!(self.board.street() == Street::Pref);
         let stack = self.actor_ref().stack();
@@ -183,6 +185,54 @@ impl Game {
         }
     }
 
+    /// would this Action be accepted here, amount and cards included?
+    /// act() only asserts that the kind of Action is available, so
+    /// anything coming from outside the engine should pass through here.
+    pub fn validate(&self, action: &Action) -> Result<(), String> {
+        let legal = self.legal();
+        if legal.is_empty() {
+            return Err("hand is already over".to_string());
+        }
+        if !legal
+            .iter()
+            .any(|o| std::mem::discriminant(o) == std::mem::discriminant(action))
+        {
+            return Err(format!(
+                "expected one of {}",
+                legal
+                    .into_iter()
+                    .map(String::from)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        match *action {
+            Action::Draw(cards) if cards.size() != self.board.street().n_revealed() => Err(
+                format!("expected {} cards", self.board.street().n_revealed()),
+            ),
+            Action::Draw(cards) if u64::from(cards) & u64::from(self.dealt()) != 0 => {
+                Err("card was already dealt".to_string())
+            }
+            Action::Blind(chips) if chips != self.next_blind() => {
+                Err(format!("blind must be {}", self.next_blind()))
+            }
+            Action::Call(chips) if chips != self.to_call() => {
+                Err(format!("call must be {}", self.to_call()))
+            }
+            Action::Shove(chips) if chips != self.to_shove() => {
+                Err(format!("shove must be {}", self.to_shove()))
+            }
+            Action::Raise(chips) if chips < self.to_raise() || chips >= self.to_shove() => {
+                Err(format!(
+                    "raise must be in [{}, {})",
+                    self.to_raise(),
+                    self.to_shove()
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
     //
     fn act(&mut self, ref a: Action) {
         log::trace!("acting {} {}", self.actor_idx(), a);
@@ -223,7 +273,7 @@ impl Game {
     }
     fn reveal(&mut self, hand: Hand) {
         // tightly coupled with next_street?
-        self.ticker = self.dealer;
+        self.ticker = 0;
         self.board.add(hand);
     }
     fn next_street(&mut self) {
@@ -260,10 +310,11 @@ impl Game {
             self.is_everyone_alright()
         }
     }
-    /// blinds have not yet been posted // TODO some edge case of all in blinds
+    /// blinds have not yet been posted. the first two seats to act
+    /// preflop are the blinds, whether or not they can cover them.
     fn is_blinding(&self) -> bool {
         if self.board.street() == Street::Pref {
-            self.pot() < Self::sblind() + Self::bblind()
+            self.ticker <= 2
         } else {
             false
         }
@@ -333,6 +384,20 @@ impl Game {
     pub fn to_shove(&self) -> Chips {
         self.actor_ref().stack()
     }
+    /// what a seat does when it can't decide: check if it's free, fold otherwise
+    pub fn passive(&self) -> Action {
+        match self.to_call() {
+            0 => Action::Check,
+            _ => Action::Fold,
+        }
+    }
+    fn next_blind(&self) -> Chips {
+        if self.pot() == 0 {
+            self.sblind
+        } else {
+            self.bblind
+        }
+    }
     pub fn to_raise(&self) -> Chips {
         let (most_large_stake, next_large_stake) = self
             .seats
@@ -350,14 +415,120 @@ impl Game {
             });
         let relative_raise = most_large_stake - self.actor().stake();
         let marginal_raise = most_large_stake - next_large_stake;
-        let required_raise = std::cmp::max(marginal_raise, Self::bblind());
+        let required_raise = std::cmp::max(marginal_raise, self.bblind);
         relative_raise + required_raise
     }
 
     //
     pub fn settlements(&self) -> Vec<Settlement> {
         assert!(self.is_terminal());
-        Showdown::from(self.ledger()).settle()
+        let rake = match self.board.street() {
+            Street::Pref => Rake::default(), // no flop, no drop
+            _ => self.rake,
+        };
+        Showdown::from((self.ledger(), rake)).settle()
+    }
+    /// each seat's average reward over every way the rest of the board
+    /// can run out, with pots split just as settlements would split them.
+    /// this only makes sense once nobody has a decision left to make.
+    ///
+    /// who gets paid depends only on how the live hands rank against
+    /// each other, so we settle once per ranking and count the rest.
+    /// we enumerate every runout. preflop there are well over a million,
+    /// so we count them in parallel and remember the counts by hole
+    /// cards, since the same all-in comes up again and again.
+    pub fn expected(&self) -> Vec<Utility> {
+        let missing = Street::Rive.n_observed() - Hand::from(self.board).size();
+        if missing == 0 {
+            return self
+                .runout(Hand::empty())
+                .into_iter()
+                .map(|r| r as Utility)
+                .collect();
+        }
+        let settled = match self.board.street() {
+            Street::Pref => self.preflop(),
+            _ => self.rankings(missing),
+        };
+        let total = settled.values().map(|(_, n)| n).sum::<usize>() as Utility;
+        settled
+            .values()
+            .map(|(runout, n)| (self.runout(*runout), n))
+            .fold(vec![0.; N], |mut expected, (rewards, n)| {
+                for (e, r) in expected.iter_mut().zip(rewards) {
+                    *e += r as Utility * *n as Utility / total;
+                }
+                expected
+            })
+    }
+    /// preflop rankings, counted once per set of holdings
+    fn preflop(&self) -> Rankings {
+        type Holdings = [(Hand, bool); N];
+        static SETTLED: OnceLock<Mutex<HashMap<Holdings, Rankings>>> = OnceLock::new();
+        let key = self
+            .seats
+            .map(|s| (Hand::from(s.cards()), s.state() == State::Folding));
+        let cache = SETTLED.get_or_init(Mutex::default);
+        if let Some(settled) = cache.lock().expect("unpoisoned").get(&key) {
+            return settled.clone();
+        }
+        let settled = self.rankings(Street::Rive.n_observed());
+        cache
+            .lock()
+            .expect("unpoisoned")
+            .insert(key, settled.clone());
+        settled
+    }
+    /// how many runouts rank the live hands each way,
+    /// with one of those runouts to settle that ranking by
+    fn rankings(&self, missing: usize) -> Rankings {
+        HandIterator::from((missing, self.dealt()))
+            .collect::<Vec<Hand>>()
+            .into_par_iter()
+            .fold(Rankings::default, |mut settled, runout| {
+                settled.entry(self.ranking(runout)).or_insert((runout, 0)).1 += 1;
+                settled
+            })
+            .reduce(Rankings::default, |mut settled, other| {
+                for (ranking, (runout, n)) in other {
+                    settled.entry(ranking).or_insert((runout, 0)).1 += n;
+                }
+                settled
+            })
+    }
+    /// how many live hands beat each seat's, once these cards are dealt
+    fn ranking(&self, runout: Hand) -> [usize; N] {
+        let board = Hand::add(Hand::from(self.board), runout);
+        let strengths = self
+            .seats
+            .map(|s| Strength::from(Hand::add(Hand::from(s.cards()), board)));
+        strengths.map(|mine| {
+            (0..N)
+                .filter(|&j| self.seats[j].state() != State::Folding)
+                .filter(|&j| strengths[j] > mine)
+                .count()
+        })
+    }
+    /// rewards once these cards are dealt, street by street
+    fn runout(&self, runout: Hand) -> Vec<Chips> {
+        let mut cards = Vec::<Card>::from(runout).into_iter();
+        let mut game = *self;
+        while game.player() == Ply::Chance {
+            let n = game.board().street().n_revealed();
+            let draw = Hand::from(cards.by_ref().take(n).collect::<Vec<Card>>());
+            game = game.apply(Action::Draw(draw));
+        }
+        game.settlements().iter().map(|s| s.reward).collect()
+    }
+    /// the main pot and any side pots, as they stand right now.
+    /// winners are filled in once the hand is over.
+    pub fn pots(&self) -> Vec<Pot> {
+        let ledger = self.ledger();
+        let mut pots = Pot::layers(&ledger);
+        if self.is_terminal() {
+            pots.iter_mut().for_each(|pot| pot.award(&ledger));
+        }
+        pots
     }
     fn ledger(&self) -> Vec<Settlement> {
         self.seats
@@ -371,6 +542,7 @@ impl Game {
             risked: seat.spent(),
             status: seat.state(),
             strength: self.strength(seat),
+            rake: 0,
         }
     }
     fn strength(&self, seat: &Seat) -> Strength {
@@ -385,15 +557,27 @@ impl Game {
         self.deck().deal(self.board().street())
     }
     fn deck(&self) -> Deck {
+        Deck::from(self.dealt().complement())
+    }
+    fn dealt(&self) -> Hand {
         let mut removed = Hand::from(self.board);
         for seat in self.seats.iter() {
             let hole = Hand::from(seat.cards());
             removed = Hand::add(removed, hole);
         }
-        Deck::from(removed.complement())
+        removed
     }
     fn actor_idx(&self) -> Position {
-        (self.dealer + self.ticker) % N
+        (self.dealer + self.ticker + Self::offset(self.board.street())) % N
+    }
+    /// heads up, the button posts the small blind and acts first
+    /// preflop, but still acts last on every street after that.
+    fn offset(street: Street) -> Position {
+        if N == 2 && street == Street::Pref {
+            1
+        } else {
+            0
+        }
     }
     fn actor_ref(&self) -> &Seat {
         let index = self.actor_idx();
@@ -428,11 +612,18 @@ impl Game {
             .expect("non-empty seats")
     }
 
-    const fn bblind() -> Chips {
-        crate::B_BLIND
+    pub fn bblind(&self) -> Chips {
+        self.bblind
+    }
+    pub fn sblind(&self) -> Chips {
+        self.sblind
+    }
+    pub fn rake(&self) -> Rake {
+        self.rake
     }
-    const fn sblind() -> Chips {
-        crate::S_BLIND
+    /// the house's cut, taken when the hand settles
+    pub fn set_rake(&mut self, rake: Rake) {
+        self.rake = rake;
     }
 }
 
@@ -465,7 +656,101 @@ mod tests {
         assert!(game.ticker != game.dealer);
         assert!(game.board().street() == Street::Pref);
         assert!(game.actor().state() == State::Betting);
-        assert!(game.pot() == Game::sblind() + Game::bblind());
+        assert!(game.pot() == game.sblind() + game.bblind());
+    }
+
+    #[test]
+    fn bijective_json() {
+        let game = Game::root();
+        let game = game.apply(Action::Raise(game.to_raise()));
+        let json = serde_json::to_string(&game).unwrap();
+        let back = serde_json::from_str::<Game>(&json).unwrap();
+        assert!(game == back);
+        assert!(game.legal() == back.legal());
+        assert!(game.actor().cards() == back.actor().cards());
+    }
+
+    #[test]
+    fn expected_all_in() {
+        let hole = |s: &str| Hole::try_from(s).unwrap();
+        let game = Game::seated([(100, hole("AsAh")), (100, hole("KsKh"))], 0, (1, 2));
+        let game = game.apply(Action::Shove(game.to_shove()));
+        let game = game.apply(Action::Shove(game.to_shove()));
+        let flop = Hand::try_from("2c7d9s").unwrap();
+        let game = game.apply(Action::Draw(flop));
+        let game = game.apply(Action::Draw(Hand::try_from("Jc").unwrap()));
+        let expected = game.expected();
+        assert!(expected[0] + expected[1] == 200.);
+        assert!((expected[0] - 200. * 42. / 44.).abs() < 1e-3);
+        let game = game.apply(Action::Draw(Hand::try_from("Kd").unwrap()));
+        assert!(game.expected() == vec![0., 200.]);
+    }
+
+    #[test]
+    fn expected_preflop_is_repeatable() {
+        let hole = |s: &str| Hole::try_from(s).unwrap();
+        let game = Game::seated([(100, hole("AsAh")), (100, hole("KsKh"))], 0, (1, 2));
+        let game = game.apply(Action::Shove(game.to_shove()));
+        let game = game.apply(Action::Shove(game.to_shove()));
+        let expected = game.expected();
+        assert!(expected == game.expected());
+        assert!((expected[0] + expected[1] - 200.).abs() < 1e-3);
+        assert!(expected[0] > 160. && expected[0] < 168.);
+    }
+
+    #[test]
+    fn pots_follow_betting() {
+        let mut deck = Deck::new();
+        let mut game = Game::seated([(50, deck.hole()), (100, deck.hole())], 0, (1, 2));
+        while game.player() != Ply::Terminal {
+            assert!(game.pots().iter().map(|p| p.amount).sum::<Chips>() == game.pot());
+            assert!(game.pots().iter().all(|p| p.winners.is_empty()));
+            let action = match game.player() {
+                Ply::Chance => Action::Draw(game.draw()),
+                _ => Action::Shove(game.to_shove()),
+            };
+            game = game.apply(action);
+        }
+        let pots = game.pots();
+        assert!(pots.len() == 2);
+        assert!(pots[0].amount == 100);
+        assert!(pots[0].eligible == vec![0, 1]);
+        assert!(!pots[0].winners.is_empty());
+        assert!(pots[1].amount == 50);
+        assert!(pots[1].winners == vec![1]);
+    }
+
+    #[test]
+    fn heads_up_button_posts_small_blind() {
+        let mut deck = Deck::new();
+        for dealer in 0..N {
+            let game = Game::seated([(100, deck.hole()), (100, deck.hole())], dealer, (1, 2));
+            let other = (dealer + 1) % N;
+            assert!(game.blinders() == (dealer, other));
+            assert!(game.seats()[dealer].stake() == 1);
+            assert!(game.seats()[other].stake() == 2);
+            assert!(game.player() == Ply::Choice(dealer));
+        }
+    }
+
+    #[test]
+    fn heads_up_button_acts_last_postflop() {
+        let mut deck = Deck::new();
+        for dealer in 0..N {
+            let other = (dealer + 1) % N;
+            let game = Game::seated([(100, deck.hole()), (100, deck.hole())], dealer, (1, 2));
+            let game = game.apply(Action::Call(1));
+            assert!(game.player() == Ply::Choice(other));
+            let mut game = game.apply(Action::Check);
+            for street in [Street::Pref, Street::Flop, Street::Turn] {
+                game = game.apply(Action::Draw(game.deck().deal(street)));
+                assert!(game.player() == Ply::Choice(other));
+                game = game.apply(Action::Check);
+                assert!(game.player() == Ply::Choice(dealer));
+                game = game.apply(Action::Check);
+            }
+            assert!(game.board().street() == Street::Rive);
+        }
     }
 
     #[test]
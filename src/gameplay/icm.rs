@@ -0,0 +1,101 @@
+use crate::Chips;
+use std::collections::HashMap;
+
+/// the Independent Chip Model, turning stacks into prize pool equity.
+///
+/// a seat finishes first with probability proportional to its stack,
+/// and then, recursively, the rest play on for the places below it
+/// (Malmuth-Harville). we memoize over the set of seats left, and only
+/// recurse as deep as there are places that pay.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Icm(Vec<f64>);
+
+/// prize for each place, first place first
+impl From<Vec<f64>> for Icm {
+    fn from(payouts: Vec<f64>) -> Self {
+        Self(payouts)
+    }
+}
+
+impl Icm {
+    pub fn payouts(&self) -> &[f64] {
+        &self.0
+    }
+    /// expected prize for each stack still in the tournament.
+    /// empty stacks never finish ahead of anyone who has chips.
+    pub fn equity(&self, stacks: &[Chips]) -> Vec<f64> {
+        assert!(stacks.len() < 64, "too many stacks to enumerate");
+        let everyone = (0..stacks.len()).fold(0u64, |mask, i| mask | 1 << i);
+        let mut memo = HashMap::new();
+        self.places(stacks, everyone, &mut memo)
+    }
+    /// equity of every seat in the mask, over the places still unclaimed
+    fn places(&self, stacks: &[Chips], mask: u64, memo: &mut HashMap<u64, Vec<f64>>) -> Vec<f64> {
+        if let Some(equity) = memo.get(&mask) {
+            return equity.clone();
+        }
+        let mut equity = vec![0.; stacks.len()];
+        let place = stacks.len() - mask.count_ones() as usize;
+        let seats = (0..stacks.len())
+            .filter(|i| mask & 1 << i != 0)
+            .collect::<Vec<usize>>();
+        let total = seats.iter().map(|&i| stacks[i] as f64).sum::<f64>();
+        if place < self.0.len() && !seats.is_empty() {
+            for &i in seats.iter() {
+                let p = match total {
+                    0. => 1. / seats.len() as f64,
+                    _ => stacks[i] as f64 / total,
+                };
+                if p == 0. {
+                    continue;
+                }
+                equity[i] += p * self.0[place];
+                for (e, rest) in equity
+                    .iter_mut()
+                    .zip(self.places(stacks, mask & !(1 << i), memo))
+                {
+                    *e += p * rest;
+                }
+            }
+        }
+        memo.insert(mask, equity.clone());
+        equity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn winner_takes_all_is_chip_share() {
+        let icm = Icm::from(vec![1.]);
+        let equity = icm.equity(&[30, 10, 60]);
+        assert!(close(equity[0], 0.3));
+        assert!(close(equity[1], 0.1));
+        assert!(close(equity[2], 0.6));
+    }
+
+    #[test]
+    fn chip_leader_is_compressed() {
+        let icm = Icm::from(vec![50., 30., 20.]);
+        let equity = icm.equity(&[50, 30, 20]);
+        assert!(close(equity.iter().sum::<f64>(), 100.));
+        assert!(equity[0] < 50.);
+        assert!(equity[2] > 20.);
+        let equity = icm.equity(&[40, 40, 40]);
+        assert!(equity.iter().all(|&e| close(e, 100. / 3.)));
+    }
+
+    #[test]
+    fn busted_stacks_take_the_last_places() {
+        let icm = Icm::from(vec![70., 30.]);
+        let equity = icm.equity(&[0, 100, 0]);
+        assert!(close(equity[1], 70.));
+        assert!(close(equity[0], 15.));
+    }
+}
@@ -0,0 +1,37 @@
+use crate::history::hand::Outcome;
+use crate::Chips;
+use crate::Utility;
+
+/// running totals for one seat over a session
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Ledger {
+    pub hands: usize,
+    pub won: Chips,
+    pub rake: Chips,
+    /// net won, with all-in hands counted at their equity
+    pub adjusted: Utility,
+}
+
+impl Ledger {
+    /// add one settled hand, and what it was worth all-in adjusted
+    pub fn record(&mut self, outcome: &Outcome, adjusted: Utility) {
+        self.hands += 1;
+        self.won += outcome.reward - outcome.risked;
+        self.rake += outcome.rake;
+        self.adjusted += adjusted;
+    }
+    /// net won per hand played
+    pub fn rate(&self) -> f32 {
+        self.won as f32 / self.hands.max(1) as f32
+    }
+}
+
+impl std::fmt::Display for Ledger {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{} hands, won {:+} ({:+.1} all-in adjusted), paid {} in rake",
+            self.hands, self.won, self.adjusted, self.rake
+        )
+    }
+}
@@ -1,6 +1,15 @@
 pub mod action;
+pub mod arena;
+pub mod event;
 pub mod game;
+pub mod icm;
+pub mod ledger;
+pub mod observer;
 pub mod ply;
+pub mod pot;
+pub mod rake;
 pub mod seat;
 pub mod settlement;
 pub mod showdown;
+pub mod table;
+pub mod tournament;
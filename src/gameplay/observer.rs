@@ -0,0 +1,26 @@
+use super::event::Event;
+use super::game::Position;
+
+/// anything that wants to hear what happens at a Table, without
+/// taking part: UIs, hand history writers, stats trackers, loggers.
+///
+/// Events arrive as they happen. an Observer that watches from a seat
+/// only hears about that seat's hole cards. one that watches from no
+/// seat hears everything, including the Finished HandHistory.
+pub trait Observer {
+    fn notify(&mut self, event: &Event);
+    /// whose private Events we get, if anyone's in particular
+    fn seat(&self) -> Option<Position> {
+        None
+    }
+}
+
+/// any closure over Events will do, watching from no seat
+impl<F> Observer for F
+where
+    F: FnMut(&Event),
+{
+    fn notify(&mut self, event: &Event) {
+        self(event)
+    }
+}
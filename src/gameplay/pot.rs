@@ -0,0 +1,133 @@
+use super::game::Position;
+use super::seat::State;
+use super::settlement::Settlement;
+use crate::Chips;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// a main or side pot, the seats still contesting it,
+/// and once the hand is over, the seats that won it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Pot {
+    pub amount: Chips,
+    pub eligible: Vec<Position>,
+    #[serde(default)]
+    pub winners: Vec<Position>,
+}
+
+impl Pot {
+    /// peel contributions into layers at each distinct all-in (or final)
+    /// level among live players. each layer is a pot contested by every
+    /// live player who risked at least that much. folded chips fall into
+    /// whichever layers they reach. chips nobody has matched sit in a
+    /// last layer of their own, with only the bettor eligible.
+    pub fn layers(settlements: &[Settlement]) -> Vec<Pot> {
+        let mut levels = settlements
+            .iter()
+            .filter(|s| s.status != State::Folding)
+            .map(|s| s.risked)
+            .collect::<Vec<Chips>>();
+        levels.sort_unstable();
+        levels.dedup();
+        let mut pots = Vec::new();
+        let mut floor = 0;
+        for level in levels {
+            let amount = settlements
+                .iter()
+                .map(|s| s.risked.min(level) - s.risked.min(floor))
+                .sum::<Chips>();
+            let eligible = settlements
+                .iter()
+                .enumerate()
+                .filter(|(_, s)| s.status != State::Folding)
+                .filter(|(_, s)| s.risked >= level)
+                .map(|(i, _)| i)
+                .collect::<Vec<Position>>();
+            if amount > 0 {
+                pots.push(Pot {
+                    amount,
+                    eligible,
+                    winners: Vec::new(),
+                });
+            }
+            floor = level;
+        }
+        pots
+    }
+    /// hand this pot to the strongest of its eligible seats, split on ties
+    pub fn award(&mut self, settlements: &[Settlement]) {
+        let best = self.eligible.iter().map(|&i| settlements[i].strength).max();
+        self.winners = self
+            .eligible
+            .iter()
+            .copied()
+            .filter(|&i| Some(settlements[i].strength) == best)
+            .collect();
+    }
+    pub fn is_split(&self) -> bool {
+        self.winners.len() > 1
+    }
+}
+
+impl std::fmt::Display for Pot {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let seats = |seats: &[Position]| {
+            seats
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<String>>()
+                .join(",")
+        };
+        write!(f, "{:>5} [{}]", self.amount, seats(&self.eligible))?;
+        if !self.winners.is_empty() {
+            write!(f, " -> [{}]", seats(&self.winners))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::kicks::Kickers;
+    use crate::cards::rank::Rank;
+    use crate::cards::ranking::Ranking;
+    use crate::cards::strength::Strength;
+
+    fn pair(rank: Rank) -> Strength {
+        Strength::from((Ranking::OnePair(rank), Kickers::default()))
+    }
+
+    #[test]
+    fn side_pots() {
+        let strength = Strength::from((Ranking::MAX, Kickers::default()));
+        let pots = Pot::layers(&[
+            Settlement::from((50, State::Shoving, strength)),
+            Settlement::from((100, State::Betting, strength)),
+            Settlement::from((100, State::Betting, strength)),
+            Settlement::from((20, State::Folding, strength)),
+        ]);
+        assert!(pots.len() == 2);
+        assert!(pots[0].amount == 170);
+        assert!(pots[0].eligible == vec![0, 1, 2]);
+        assert!(pots[1].amount == 100);
+        assert!(pots[1].eligible == vec![1, 2]);
+    }
+
+    #[test]
+    fn short_stack_wins_main_pot() {
+        let settlements = [
+            Settlement::from((50, State::Shoving, pair(Rank::Ace))),
+            Settlement::from((100, State::Betting, pair(Rank::King))),
+            Settlement::from((100, State::Betting, pair(Rank::King))),
+            Settlement::from((100, State::Folding, pair(Rank::Ace))),
+        ];
+        let mut pots = Pot::layers(&settlements);
+        pots.iter_mut().for_each(|p| p.award(&settlements));
+        assert!(pots[0].amount == 200);
+        assert!(pots[0].winners == vec![0]);
+        assert!(pots[1].amount == 150);
+        assert!(pots[1].winners == vec![1, 2]);
+        assert!(pots[1].is_split());
+    }
+}
@@ -0,0 +1,39 @@
+use crate::Chips;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// the house's cut of every pot: a share of the contested chips,
+/// up to a cap per hand. the default takes nothing.
+///
+/// only hands that see a flop are raked, and chips that nobody
+/// called are returned whole. see Showdown for how the rake comes
+/// out of the main pot first, and then out of any side pots.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Rake {
+    pub rate: f32,
+    pub cap: Chips,
+}
+
+impl From<(f32, Chips)> for Rake {
+    fn from((rate, cap): (f32, Chips)) -> Self {
+        assert!((0. ..=1.).contains(&rate), "rake is a share of the pot");
+        Self { rate, cap }
+    }
+}
+
+impl Rake {
+    pub fn is_free(&self) -> bool {
+        self.rate == 0. || self.cap == 0
+    }
+    /// rake on this many contested chips, when some has been taken already
+    pub fn take(&self, contested: Chips, taken: Chips) -> Chips {
+        let rake = (contested as f32 * self.rate) as Chips;
+        rake.min(self.cap - taken).max(0)
+    }
+}
+
+impl std::fmt::Display for Rake {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:.1}% up to {}", self.rate * 100., self.cap)
+    }
+}
@@ -2,7 +2,7 @@ use crate::cards::hole::Hole;
 use crate::Chips;
 use colored::Colorize;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct Seat {
     cards: Hole,
     state: State,
@@ -77,7 +77,7 @@ impl std::fmt::Display for Seat {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum State {
     Betting,
     Shoving,
@@ -9,6 +9,8 @@ pub struct Settlement {
     pub risked: Chips,
     pub status: State,
     pub strength: Strength,
+    /// taken out of this seat's winnings by the house
+    pub rake: Chips,
 }
 
 impl Settlement {
@@ -24,6 +26,7 @@ impl From<(Chips, State, Strength)> for Settlement {
             risked,
             status,
             strength,
+            rake: 0,
         }
     }
 }
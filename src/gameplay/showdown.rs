@@ -1,6 +1,7 @@
 use crate::cards::kicks::Kickers;
 use crate::cards::ranking::Ranking;
 use crate::cards::strength::Strength;
+use crate::gameplay::rake::Rake;
 use crate::gameplay::seat::State;
 use crate::gameplay::settlement::Settlement;
 use crate::Chips;
@@ -11,15 +12,27 @@ pub struct Showdown {
     distributing: Chips,
     distributed: Chips,
     best: Strength,
+    rake: Rake,
+    raked: Chips,
 }
 
 impl From<Vec<Settlement>> for Showdown {
     fn from(payouts: Vec<Settlement>) -> Self {
+        Self::from((payouts, Rake::default()))
+    }
+}
+
+/// rake comes out of each pot as it is awarded, main pot first,
+/// until the cap is reached. chips nobody called are never raked.
+impl From<(Vec<Settlement>, Rake)> for Showdown {
+    fn from((payouts, rake): (Vec<Settlement>, Rake)) -> Self {
         Self {
             payouts,
             distributing: 0 as Chips,
             distributed: 0 as Chips,
             best: Strength::from((Ranking::MAX, Kickers::default())),
+            rake,
+            raked: 0 as Chips,
         }
     }
 }
@@ -66,8 +79,22 @@ impl Showdown {
             .map(|s| (s - self.distributed).max(0))
             .sum()
     }
+    /// chips in this pot that somebody else matched
+    fn contested(&self) -> Chips {
+        let mut risked = self.payouts.iter().map(|p| p.risked).collect::<Vec<_>>();
+        risked.sort_unstable();
+        let called = risked.iter().rev().nth(1).copied().unwrap_or(0);
+        let top = self.distributing.min(called);
+        let low = self.distributed.min(called);
+        self.payouts
+            .iter()
+            .map(|p| p.risked.min(top) - p.risked.min(low))
+            .sum()
+    }
     fn distribute(&mut self) {
-        let chips = self.winnings();
+        let rake = self.rake.take(self.contested(), self.raked);
+        let chips = self.winnings() - rake;
+        self.raked += rake;
         let mut winners = self
             .payouts
             .iter_mut()
@@ -75,20 +102,17 @@ impl Showdown {
             .filter(|p| p.strength == self.best)
             .filter(|p| p.risked > self.distributed)
             .collect::<Vec<&mut Settlement>>();
-        let n = winners.len();
-        let share = chips / n as Chips;
-        let bonus = chips % n as Chips;
-        for winner in winners.iter_mut() {
-            winner.reward += share;
-        }
-        for winner in winners.iter_mut().take(bonus as usize) {
-            winner.reward += 1;
+        let n = winners.len() as Chips;
+        for (i, winner) in winners.iter_mut().enumerate() {
+            let i = i as Chips;
+            winner.reward += chips / n + (i < chips % n) as Chips;
+            winner.rake += rake / n + (i < rake % n) as Chips;
         }
     }
     fn is_complete(&self) -> bool {
         let staked = self.payouts.iter().map(|p| p.risked).sum::<Chips>();
         let reward = self.payouts.iter().map(|p| p.reward).sum::<Chips>();
-        staked == reward
+        staked == reward + self.raked
     }
 }
 
@@ -225,6 +249,62 @@ mod tests {
         assert!(settlement[2].reward == 50);
     }
 
+    #[test]
+    fn raked_up_to_the_cap() {
+        let rake = Rake::from((0.05, 3));
+        let settlement = Showdown::from((
+            vec![
+                Settlement::from((20, State::Betting, ace_high())),
+                Settlement::from((20, State::Betting, one_pair())),
+            ],
+            rake,
+        ))
+        .settle();
+        assert!(settlement[1].reward == 38);
+        assert!(settlement[1].rake == 2);
+        let settlement = Showdown::from((
+            vec![
+                Settlement::from((100, State::Betting, ace_high())),
+                Settlement::from((100, State::Betting, one_pair())),
+            ],
+            rake,
+        ))
+        .settle();
+        assert!(settlement[1].reward == 197);
+        assert!(settlement[1].rake == 3);
+    }
+
+    #[test]
+    fn uncalled_chips_are_not_raked() {
+        let settlement = Showdown::from((
+            vec![
+                Settlement::from((100, State::Betting, ace_high())),
+                Settlement::from((20, State::Folding, one_pair())),
+            ],
+            Rake::from((0.05, 10)),
+        ))
+        .settle();
+        assert!(settlement[0].reward == 118);
+        assert!(settlement[0].rake == 2);
+    }
+
+    #[test]
+    fn main_pot_is_raked_first() {
+        let settlement = Showdown::from((
+            vec![
+                Settlement::from((50, State::Shoving, the_nuts())),
+                Settlement::from((100, State::Betting, two_pair())),
+                Settlement::from((100, State::Betting, one_pair())),
+            ],
+            Rake::from((0.1, 20)),
+        ))
+        .settle();
+        assert!(settlement[0].reward == 135);
+        assert!(settlement[0].rake == 15);
+        assert!(settlement[1].reward == 95);
+        assert!(settlement[1].rake == 5);
+    }
+
     #[test]
     fn last_man_standing() {
         let settlement = Showdown::from(vec![
@@ -0,0 +1,419 @@
+use super::action::Action;
+use super::event::Event;
+use super::game::Game;
+use super::game::Position;
+use super::ledger::Ledger;
+use super::observer::Observer;
+use super::ply::Ply;
+use super::rake::Rake;
+use crate::cards::deck::Deck;
+use crate::cards::hand::Hand;
+use crate::gameplay::seat::State;
+use crate::history::hand::HandHistory;
+use crate::history::replay::Replay;
+use crate::players::player::Player;
+use crate::Chips;
+use crate::N;
+
+/// a table of Players, dealing one hand after another.
+///
+/// the Table holds everyone's chips in between hands. each hand is
+/// dealt from a fresh Deck into a new Game, seated with the current
+/// stacks, button, and blinds, and the button moves one seat per hand.
+/// a seat with no chips left is busted: it reloads if the Table allows
+/// it, otherwise the session is over. Observers hear every Event
+/// of every hand as it happens.
+pub struct Table {
+    players: Vec<Box<dyn Player>>,
+    observers: Vec<Box<dyn Observer>>,
+    stacks: Vec<Chips>,
+    button: Position,
+    blinds: (Chips, Chips),
+    reload: Option<Chips>,
+    rake: Rake,
+    ledgers: Vec<Ledger>,
+    hands: usize,
+}
+
+impl From<Vec<Box<dyn Player>>> for Table {
+    fn from(players: Vec<Box<dyn Player>>) -> Self {
+        assert!(players.len() == N, "tables have {} seats", N);
+        Self {
+            stacks: vec![crate::STACK; players.len()],
+            players,
+            observers: Vec::new(),
+            button: 0,
+            blinds: (crate::S_BLIND, crate::B_BLIND),
+            reload: None,
+            rake: Rake::default(),
+            ledgers: vec![Ledger::default(); N],
+            hands: 0,
+        }
+    }
+}
+
+impl Table {
+    pub fn stacks(&self) -> &[Chips] {
+        &self.stacks
+    }
+    pub fn button(&self) -> Position {
+        self.button
+    }
+    pub fn blinds(&self) -> (Chips, Chips) {
+        self.blinds
+    }
+    /// how many hands have been dealt so far
+    pub fn hands(&self) -> usize {
+        self.hands
+    }
+    pub fn set_blinds(&mut self, blinds: (Chips, Chips)) {
+        self.blinds = blinds;
+    }
+    pub fn set_rake(&mut self, rake: Rake) {
+        self.rake = rake;
+    }
+    /// what each seat has won and paid in rake so far
+    pub fn ledgers(&self) -> &[Ledger] {
+        &self.ledgers
+    }
+    /// top busted seats back up to this many chips, cash game style
+    pub fn set_reload(&mut self, stack: Option<Chips>) {
+        self.reload = stack;
+    }
+    /// hear about every hand from now on
+    pub fn subscribe(&mut self, observer: Box<dyn Observer>) {
+        self.observers.push(observer);
+    }
+    /// is anyone out of chips, with no way to reload?
+    pub fn is_busted(&self) -> bool {
+        self.reload.is_none() && self.stacks.contains(&0)
+    }
+
+    /// deal and play out one hand, unless someone is busted
+    pub fn play(&mut self) -> Option<HandHistory> {
+        if let Some(stack) = self.reload {
+            self.stacks
+                .iter_mut()
+                .filter(|s| **s == 0)
+                .for_each(|s| *s = stack);
+        }
+        if self.is_busted() {
+            return None;
+        }
+        let mut deck = Deck::new();
+        let mut seats = [(0, deck.hole()); N];
+        for (seat, stack) in seats.iter_mut().zip(self.stacks.iter()) {
+            *seat = (*stack, deck.hole());
+        }
+        let mut game = Game::seated(seats, self.button, self.blinds);
+        game.set_rake(self.rake);
+        let replay = Self::record(
+            &mut self.players,
+            &mut self.observers,
+            game,
+            self.hands,
+            |game| game.draw(),
+        );
+        let adjusted = replay.adjusted();
+        let hand = HandHistory::from(replay);
+        for ((ledger, outcome), ev) in self.ledgers.iter_mut().zip(&hand.results).zip(adjusted) {
+            ledger.record(outcome, ev);
+        }
+        for (stack, (entry, outcome)) in self
+            .stacks
+            .iter_mut()
+            .zip(hand.seats.iter().zip(hand.results.iter()))
+        {
+            *stack = entry.stack - outcome.risked + outcome.reward;
+        }
+        self.button = (self.button + 1) % N;
+        self.hands += 1;
+        Some(hand)
+    }
+
+    /// play out a hand that has just been seated, with board cards
+    /// coming from runout. Players hear about it exactly as they
+    /// would at a Table. an illegal decision is logged and replaced
+    /// by checking if that's free and folding if not, as Clock does.
+    /// Observers hear about it as Events.
+    pub fn deal(
+        players: &mut [Box<dyn Player>],
+        observers: &mut [Box<dyn Observer>],
+        game: Game,
+        id: usize,
+        runout: impl FnMut(&Game) -> Hand,
+    ) -> HandHistory {
+        HandHistory::from(Self::record(players, observers, game, id, runout))
+    }
+    /// deal() a hand, keeping every Game state it went through,
+    /// e.g. to all-in adjust it without replaying it first
+    pub fn record(
+        players: &mut [Box<dyn Player>],
+        observers: &mut [Box<dyn Observer>],
+        game: Game,
+        id: usize,
+        mut runout: impl FnMut(&Game) -> Hand,
+    ) -> Replay {
+        let mut game = game;
+        let mut games = vec![game];
+        let mut hand = HandHistory::from((id, &game));
+        for (seat, player) in players.iter_mut().enumerate() {
+            player.begin(&game.seen_by(seat));
+        }
+        Self::publish(
+            observers,
+            Event::Started {
+                hand: id,
+                dealer: hand.dealer,
+                blinds: hand.blinds,
+                stacks: hand.seats.iter().map(|e| e.stack).collect(),
+            },
+        );
+        for entry in hand.seats.iter() {
+            let (seat, hole) = (entry.seat, entry.hole);
+            Self::publish(observers, Event::Dealt { seat, hole });
+        }
+        for play in hand.posted() {
+            players.iter_mut().for_each(|p| p.observe(play));
+            let (seat, action) = (play.seat.expect("blinds are posted"), play.action);
+            Self::publish(observers, Event::Acted { seat, action });
+        }
+        loop {
+            let action = match game.player() {
+                Ply::Chance => Action::Draw(runout(&game)),
+                Ply::Choice(seat) => {
+                    let action = players[seat].decide(&game.seen_by(seat));
+                    match game.validate(&action) {
+                        Ok(()) => action,
+                        Err(reason) => {
+                            log::warn!("seat {} chose illegal {}: {}", seat, action, reason);
+                            game.passive()
+                        }
+                    }
+                }
+                Ply::Terminal => break,
+            };
+            hand.record(&game, action);
+            game = game.apply(action);
+            games.push(game);
+            let play = hand.plays.last().expect("just recorded");
+            players.iter_mut().for_each(|p| p.observe(play));
+            Self::publish(
+                observers,
+                match (play.seat, play.action) {
+                    (Some(seat), action) => Event::Acted { seat, action },
+                    (None, _) => Event::Advanced {
+                        street: game.board().street(),
+                        board: game.board(),
+                    },
+                },
+            );
+        }
+        hand.settle(&game);
+        if hand.is_showdown() {
+            let shown = hand
+                .seats
+                .iter()
+                .zip(hand.results.iter())
+                .filter(|(_, outcome)| outcome.status != State::Folding)
+                .map(|(entry, _)| (entry.seat, entry.hole))
+                .collect();
+            Self::publish(observers, Event::Showdown { shown });
+        }
+        let pots = hand.pots.clone();
+        Self::publish(observers, Event::Awarded { pots });
+        Self::publish(observers, Event::Finished(hand.clone()));
+        Replay::from((hand, games))
+    }
+    /// tell each Observer, as long as it's allowed to know
+    fn publish(observers: &mut [Box<dyn Observer>], event: Event) {
+        observers
+            .iter_mut()
+            .filter(|o| event.is_visible(o.seat()))
+            .for_each(|o| o.notify(&event));
+    }
+
+    /// play up to this many hands, stopping early if someone busts
+    pub fn session(&mut self, hands: usize) -> Vec<HandHistory> {
+        (0..hands).map_while(|_| self.play()).collect()
+    }
+
+    /// play until someone busts, saving every hand as we go
+    pub fn run(&mut self) {
+        while let Some(hand) = self.play() {
+            hand.save();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::street::Street;
+    use crate::players::caller::Caller;
+    use crate::players::random::Random;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn shove(game: &Game) -> Action {
+        game.legal()
+            .into_iter()
+            .find(|a| matches!(a, Action::Shove(_)))
+            .unwrap_or(Action::Check)
+    }
+
+    #[test]
+    fn chips_are_conserved() {
+        let mut table = Table::from(vec![
+            Box::new(Random) as Box<dyn Player>,
+            Box::new(Random) as Box<dyn Player>,
+        ]);
+        table.set_reload(Some(crate::STACK));
+        let hands = table.session(100);
+        assert!(hands.len() == 100);
+        assert!(table.hands() == 100);
+        assert!(hands
+            .iter()
+            .all(|h| h.settlements().iter().map(|s| s.pnl()).sum::<Chips>() == 0));
+        assert!(hands.windows(2).all(|w| w[0].dealer != w[1].dealer));
+    }
+
+    #[test]
+    fn rake_is_accounted() {
+        let mut table = Table::from(vec![
+            Box::new(Caller) as Box<dyn Player>,
+            Box::new(Caller) as Box<dyn Player>,
+        ]);
+        table.set_reload(Some(crate::STACK));
+        table.set_rake(Rake::from((0.25, 3)));
+        let hands = table.session(50);
+        let raked = hands.iter().map(|h| h.raked()).sum::<Chips>();
+        let ledgers = table.ledgers();
+        assert!(raked > 0);
+        assert!(hands.iter().all(|h| h.raked() <= 3));
+        assert!(ledgers.iter().all(|l| l.hands == 50));
+        assert!(ledgers.iter().map(|l| l.rake).sum::<Chips>() == raked);
+        assert!(ledgers.iter().map(|l| l.won).sum::<Chips>() == -raked);
+        assert!((ledgers.iter().map(|l| l.adjusted).sum::<f32>() + raked as f32).abs() < 1e-2);
+    }
+
+    #[test]
+    fn no_flop_no_drop() {
+        let fold = |game: &Game| match game.to_call() {
+            0 => Action::Check,
+            _ => Action::Fold,
+        };
+        let mut table = Table::from(vec![
+            Box::new(fold) as Box<dyn Player>,
+            Box::new(fold) as Box<dyn Player>,
+        ]);
+        table.set_rake(Rake::from((0.05, 3)));
+        let hand = table.play().expect("nobody busted yet");
+        assert!(hand.board.street() == crate::cards::street::Street::Pref);
+        assert!(hand.raked() == 0);
+        assert!(table.ledgers().iter().map(|l| l.won).sum::<Chips>() == 0);
+    }
+
+    struct Seated(Position, Rc<RefCell<Vec<Event>>>);
+    impl Observer for Seated {
+        fn notify(&mut self, event: &Event) {
+            self.1.borrow_mut().push(event.clone());
+        }
+        fn seat(&self) -> Option<Position> {
+            Some(self.0)
+        }
+    }
+
+    #[test]
+    fn observers_hear_events() {
+        let everything = Rc::new(RefCell::new(Vec::new()));
+        let private = Rc::new(RefCell::new(Vec::new()));
+        let mut table = Table::from(vec![
+            Box::new(Caller) as Box<dyn Player>,
+            Box::new(Caller) as Box<dyn Player>,
+        ]);
+        let heard = everything.clone();
+        table.subscribe(Box::new(move |e: &Event| {
+            heard.borrow_mut().push(e.clone())
+        }));
+        table.subscribe(Box::new(Seated(1, private.clone())));
+        let hand = table.play().expect("nobody busted yet");
+        let everything = everything.borrow();
+        let private = private.borrow();
+        let dealt = |events: &[Event]| {
+            events
+                .iter()
+                .filter(|e| matches!(e, Event::Dealt { .. }))
+                .count()
+        };
+        assert!(matches!(everything[0], Event::Started { hand: 0, .. }));
+        assert!(dealt(&everything) == N);
+        assert!(dealt(&private) == 1);
+        assert!(private.contains(&Event::Dealt {
+            seat: 1,
+            hole: hand.seats[1].hole
+        }));
+        assert!(everything
+            .iter()
+            .any(|e| matches!(e, Event::Showdown { shown } if shown.len() == N)));
+        assert!(everything.iter().any(|e| matches!(
+            e,
+            Event::Advanced {
+                street: Street::Rive,
+                ..
+            }
+        )));
+        assert!(everything.last() == Some(&Event::Finished(hand.clone())));
+        assert!(private.last() == Some(&Event::Awarded { pots: hand.pots }));
+        assert!(!private.iter().any(|e| matches!(e, Event::Finished(_))));
+    }
+
+    #[test]
+    fn busted_ends_session() {
+        let mut table = Table::from(vec![
+            Box::new(shove) as Box<dyn Player>,
+            Box::new(shove) as Box<dyn Player>,
+        ]);
+        let hands = table.session(1_000);
+        assert!(hands.len() < 1_000);
+        assert!(table.is_busted());
+        assert!(table.stacks().iter().sum::<Chips>() == N as Chips * crate::STACK);
+        assert!(table.play().is_none());
+    }
+
+    #[test]
+    fn recorded_hands_replay() {
+        let mut players = [Box::new(shove) as Box<dyn Player>, Box::new(Caller)];
+        let replay = Table::record(&mut players, &mut [], Game::root(), 0, |g| g.draw());
+        let hand = HandHistory::from(replay.clone());
+        let again = Replay::try_from(&hand).unwrap();
+        assert!(replay.games() == again.games());
+        assert!(replay.adjusted() == again.adjusted());
+    }
+
+    #[test]
+    fn illegal_actions_are_passive() {
+        let mut table = Table::from(vec![
+            Box::new(|_: &Game| Action::Raise(1)) as Box<dyn Player>,
+            Box::new(Caller) as Box<dyn Player>,
+        ]);
+        let hand = table.play().expect("nobody busted yet");
+        assert!(hand.plays.iter().all(|p| p.action != Action::Raise(1)));
+        assert!(hand.plays.iter().any(|p| p.action == Action::Fold));
+        assert!(table.stacks().iter().sum::<Chips>() == N as Chips * crate::STACK);
+    }
+
+    #[test]
+    fn all_in_blinds() {
+        let mut table = Table::from(vec![
+            Box::new(shove) as Box<dyn Player>,
+            Box::new(shove) as Box<dyn Player>,
+        ]);
+        table.stacks = vec![1, 2 * crate::STACK - 1];
+        table.button = 1;
+        let hand = table.play().expect("nobody busted yet");
+        assert!(hand.posted().len() == 2);
+        assert!(hand.posted()[1].action == Action::Shove(1));
+        assert!(table.stacks().iter().sum::<Chips>() == 2 * crate::STACK);
+    }
+}
@@ -0,0 +1,269 @@
+use super::game::Game;
+use super::game::Position;
+use super::icm::Icm;
+use super::table::Table;
+use crate::cards::deck::Deck;
+use crate::players::player::Player;
+use crate::Chips;
+use crate::N;
+
+/// one step of the blind schedule
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Level {
+    pub blinds: (Chips, Chips),
+    pub ante: Chips,
+    pub hands: usize,
+}
+
+impl From<((Chips, Chips), Chips, usize)> for Level {
+    fn from((blinds, ante, hands): ((Chips, Chips), Chips, usize)) -> Self {
+        Self {
+            blinds,
+            ante,
+            hands,
+        }
+    }
+}
+
+/// a freezeout between any number of Players.
+///
+/// our tables are heads-up, so every round the survivors are paired up
+/// at random, with one sitting out if there's an odd number, and each
+/// table plays one hand. tables break as players bust, down to the last
+/// one. the blinds follow the schedule, one Level after another, and
+/// stay at the last Level once it runs out.
+///
+/// the engine has no antes, so they are taken from every seat before
+/// the hand as dead money and handed out with the pot, in proportion to
+/// what each seat won. nobody antes their last chip. players don't see
+/// the antes in the pot they're shown.
+pub struct Tournament {
+    players: Vec<Option<Box<dyn Player>>>,
+    stacks: Vec<Chips>,
+    buttons: Vec<usize>,
+    schedule: Vec<Level>,
+    busted: Vec<Position>,
+    rounds: usize,
+    hands: usize,
+}
+
+impl From<Vec<Box<dyn Player>>> for Tournament {
+    fn from(players: Vec<Box<dyn Player>>) -> Self {
+        assert!(
+            players.len() >= N,
+            "a tournament needs at least {} players",
+            N
+        );
+        Self {
+            stacks: vec![crate::STACK; players.len()],
+            buttons: vec![0; players.len()],
+            players: players.into_iter().map(Some).collect(),
+            schedule: vec![Level::from((
+                (crate::S_BLIND, crate::B_BLIND),
+                0,
+                usize::MAX,
+            ))],
+            busted: Vec::new(),
+            rounds: 0,
+            hands: 0,
+        }
+    }
+}
+
+impl Tournament {
+    pub fn stacks(&self) -> &[Chips] {
+        &self.stacks
+    }
+    pub fn set_stack(&mut self, stack: Chips) {
+        assert!(self.rounds == 0, "stacks are set before the first hand");
+        self.stacks.iter_mut().for_each(|s| *s = stack);
+    }
+    pub fn set_schedule(&mut self, schedule: Vec<Level>) {
+        assert!(!schedule.is_empty(), "schedule needs at least one level");
+        self.schedule = schedule;
+    }
+    /// the Level we're playing at, given how many rounds have been dealt
+    pub fn level(&self) -> Level {
+        let mut rounds = self.rounds;
+        for level in self.schedule.iter() {
+            match rounds.checked_sub(level.hands) {
+                Some(rest) => rounds = rest,
+                None => return *level,
+            }
+        }
+        *self.schedule.last().expect("non-empty schedule")
+    }
+    /// seats that still have chips
+    pub fn alive(&self) -> Vec<Position> {
+        (0..self.stacks.len())
+            .filter(|&i| self.stacks[i] > 0)
+            .collect()
+    }
+    pub fn is_over(&self) -> bool {
+        self.alive().len() < N
+    }
+    /// hands dealt across all tables so far
+    pub fn hands(&self) -> usize {
+        self.hands
+    }
+    /// every seat, from first place down to the first one out.
+    /// seats still alive are ranked by their stacks.
+    pub fn standings(&self) -> Vec<Position> {
+        let mut alive = self.alive();
+        alive.sort_by_key(|&i| std::cmp::Reverse(self.stacks[i]));
+        alive
+            .into_iter()
+            .chain(self.busted.iter().rev().copied())
+            .collect()
+    }
+    /// prize pool equity of every seat. places already decided
+    /// are paid out, and whoever is left splits the rest by ICM.
+    pub fn equity(&self, icm: &Icm) -> Vec<f64> {
+        let alive = self.alive();
+        let payouts = icm.payouts();
+        let mut equity = vec![0.; self.stacks.len()];
+        for (place, seat) in self.standings().into_iter().enumerate().skip(alive.len()) {
+            equity[seat] = payouts.get(place).copied().unwrap_or(0.);
+        }
+        let stacks = alive
+            .iter()
+            .map(|&i| self.stacks[i])
+            .collect::<Vec<Chips>>();
+        let remaining = Icm::from(
+            payouts
+                .iter()
+                .take(alive.len())
+                .copied()
+                .collect::<Vec<f64>>(),
+        );
+        for (seat, e) in alive.into_iter().zip(remaining.equity(&stacks)) {
+            equity[seat] = e;
+        }
+        equity
+    }
+
+    /// play one hand at every table, unless the tournament is over
+    pub fn round(&mut self) -> bool {
+        if self.is_over() {
+            return false;
+        }
+        use rand::seq::SliceRandom;
+        let level = self.level();
+        let mut alive = self.alive();
+        alive.shuffle(&mut rand::thread_rng());
+        let before = self.stacks.clone();
+        for pair in alive.chunks_exact(N) {
+            self.hand([pair[0], pair[1]], level);
+        }
+        let mut out = alive
+            .into_iter()
+            .filter(|&i| self.stacks[i] == 0)
+            .collect::<Vec<Position>>();
+        out.sort_by_key(|&i| before[i]);
+        self.busted.extend(out);
+        self.rounds += 1;
+        true
+    }
+    /// play until one player has all the chips, and rank everyone
+    pub fn run(&mut self) -> Vec<Position> {
+        while self.round() {}
+        self.standings()
+    }
+
+    /// one heads-up hand between two entrants. whoever has had
+    /// the button less often gets it this time.
+    fn hand(&mut self, entrants: [Position; N], level: Level) {
+        let dealer = if self.buttons[entrants[1]] < self.buttons[entrants[0]] {
+            1
+        } else {
+            0
+        };
+        self.buttons[entrants[dealer]] += 1;
+        let antes = entrants.map(|i| level.ante.min(self.stacks[i] - 1).max(0));
+        let mut deck = Deck::new();
+        let seats = [0, 1].map(|s| (self.stacks[entrants[s]] - antes[s], deck.hole()));
+        let game = Game::seated(seats, dealer, level.blinds);
+        let mut players = entrants.map(|i| self.players[i].take().expect("seated once"));
+        let hand = Table::deal(&mut players, &mut [], game, self.hands, |game| game.draw());
+        for (i, player) in entrants.into_iter().zip(players) {
+            self.players[i] = Some(player);
+        }
+        let dead = antes.iter().sum::<Chips>();
+        let won = hand.results.iter().map(|o| o.reward).sum::<Chips>();
+        let mut shares = hand
+            .results
+            .iter()
+            .map(|o| dead * o.reward / won)
+            .collect::<Vec<Chips>>();
+        let best = (0..N)
+            .max_by_key(|&s| hand.results[s].reward)
+            .expect("seats");
+        shares[best] += dead - shares.iter().sum::<Chips>();
+        for (s, i) in entrants.into_iter().enumerate() {
+            let outcome = hand.results[s];
+            self.stacks[i] = seats[s].0 - outcome.risked + outcome.reward + shares[s];
+        }
+        self.hands += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::players::caller::Caller;
+    use crate::players::random::Random;
+
+    fn field(n: usize) -> Tournament {
+        Tournament::from(
+            (0..n)
+                .map(|i| match i % 2 {
+                    0 => Box::new(Random) as Box<dyn Player>,
+                    _ => Box::new(Caller) as Box<dyn Player>,
+                })
+                .collect::<Vec<Box<dyn Player>>>(),
+        )
+    }
+
+    #[test]
+    fn freezeout_to_the_end() {
+        let mut tournament = field(5);
+        tournament.set_schedule(vec![
+            Level::from(((1, 2), 0, 5)),
+            Level::from(((5, 10), 1, 5)),
+            Level::from(((25, 50), 5, 1)),
+        ]);
+        let standings = tournament.run();
+        let mut ranked = standings.clone();
+        ranked.sort();
+        assert!(ranked == (0..5).collect::<Vec<_>>());
+        assert!(tournament.alive() == vec![standings[0]]);
+        assert!(tournament.stacks().iter().sum::<Chips>() == 5 * crate::STACK);
+    }
+
+    #[test]
+    fn levels_follow_schedule() {
+        let mut tournament = field(2);
+        tournament.set_schedule(vec![
+            Level::from(((1, 2), 0, 2)),
+            Level::from(((2, 4), 1, 3)),
+        ]);
+        let levels = (0..7)
+            .map(|r| {
+                tournament.rounds = r;
+                tournament.level().blinds
+            })
+            .collect::<Vec<_>>();
+        assert!(levels == vec![(1, 2), (1, 2), (2, 4), (2, 4), (2, 4), (2, 4), (2, 4)]);
+    }
+
+    #[test]
+    fn equity_pays_busted_places() {
+        let mut tournament = field(3);
+        tournament.stacks = vec![0, 200, 100];
+        tournament.busted = vec![0];
+        let equity = tournament.equity(&Icm::from(vec![50., 30., 20.]));
+        assert!(equity[0] == 20.);
+        assert!((equity.iter().sum::<f64>() - 100.).abs() < 1e-9);
+        assert!(equity[1] > equity[2]);
+    }
+}
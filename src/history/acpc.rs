@@ -0,0 +1,64 @@
+use super::hand::HandHistory;
+use crate::acpc::state::MatchState;
+
+/// a hand from an ACPC dealer log, one line per hand:
+///
+/// `STATE:12:r250c/cr500c/cc/r1250f:Ks6h|Qs5d/8dAs9s/4c/Th:500|-500:alice|bob`
+///
+/// betting and cards are written just as in the MATCHSTATE protocol,
+/// with the first player listed as the big blind. amounts are scaled
+/// down by their common divisor, so 20000 chip stacks with 50/100 blinds
+/// come out as 400 chip stacks with 1/2 blinds.
+pub struct Acpc(HandHistory);
+
+impl From<Acpc> for HandHistory {
+    fn from(acpc: Acpc) -> Self {
+        acpc.0
+    }
+}
+
+/// str isomorphism, up to the holes we never saw
+impl TryFrom<&str> for Acpc {
+    type Error = String;
+    fn try_from(line: &str) -> Result<Self, Self::Error> {
+        match line.trim().split(':').collect::<Vec<&str>>().as_slice() {
+            ["STATE", id, betting, cards, ..] => {
+                let id = id
+                    .parse()
+                    .map_err(|_| format!("invalid hand number: {}", id))?;
+                let state = MatchState::parse(0, id, betting, cards)?;
+                HandHistory::try_from(state.draft()?).map(Self)
+            }
+            _ => Err(format!("invalid ACPC state: {}", line)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::street::Street;
+    use crate::history::replay::Replay;
+
+    #[test]
+    fn replay_folded() {
+        let line = "STATE:12:r250c/cr500c/cc/r1250f:Ks6h|Qs5d/8dAs9s/4c/Th:500|-500:alice|bob";
+        let hand = HandHistory::from(Acpc::try_from(line).unwrap());
+        assert!(hand.id == 12);
+        assert!(hand.blinds == (1, 2));
+        assert!(hand.seats.iter().all(|e| e.stack == 400));
+        let replay = Replay::try_from(&hand).unwrap();
+        assert!(replay.is_complete());
+        assert!(replay.hand().results[0].reward == replay.hand().total());
+    }
+
+    #[test]
+    fn replay_allin() {
+        let line = "STATE:3:r20000c///:AsAd|KsKd/2c3c4h/9s/Td:20000|-20000:alice|bob";
+        let hand = HandHistory::from(Acpc::try_from(line).unwrap());
+        let replay = Replay::try_from(&hand).unwrap();
+        assert!(replay.is_complete());
+        assert!(replay.hand().board.street() == Street::Rive);
+        assert!(replay.hand().results[0].reward == 2 * 400);
+    }
+}
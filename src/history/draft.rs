@@ -0,0 +1,150 @@
+use super::hand::Entry;
+use super::hand::HandHistory;
+use super::hand::Play;
+use crate::cards::board::Board;
+use crate::cards::deck::Deck;
+use crate::cards::hand::Hand;
+use crate::cards::hole::Hole;
+use crate::cards::street::Street;
+use crate::gameplay::action::Action;
+use crate::gameplay::game::Position;
+use crate::gameplay::rake::Rake;
+use crate::Chips;
+
+/// a hand history as pieced together from some external format,
+/// before it is checked against the engine.
+///
+/// amounts are kept exactly as written (in cents, for formats with
+/// decimals) and only scaled down to Chips once the whole hand is known,
+/// by their greatest common divisor. hole cards we never saw are dealt
+/// at random from whatever is left in the deck.
+#[derive(Debug, Clone, Default)]
+pub struct Draft {
+    pub id: usize,
+    pub time: u64,
+    pub dealer: Position,
+    pub blinds: (i64, i64),
+    pub stacks: Vec<i64>,
+    pub holes: Vec<Option<Hole>>,
+    pub plays: Vec<(Street, Option<Position>, Raw)>,
+}
+
+/// an Action whose amount has not been scaled yet
+#[derive(Debug, Clone, Copy)]
+pub enum Raw {
+    Chips(fn(Chips) -> Action, i64),
+    Exact(Action),
+}
+
+impl Draft {
+    /// the common divisor that brings every amount into Chips
+    fn scale(&self) -> i64 {
+        fn gcd(a: i64, b: i64) -> i64 {
+            if b == 0 {
+                a
+            } else {
+                gcd(b, a % b)
+            }
+        }
+        self.stacks
+            .iter()
+            .copied()
+            .chain([self.blinds.0, self.blinds.1])
+            .chain(self.plays.iter().filter_map(|(_, _, raw)| match raw {
+                Raw::Chips(_, amount) => Some(*amount),
+                Raw::Exact(_) => None,
+            }))
+            .fold(0, |a, b| gcd(a, b.abs()))
+            .max(1)
+    }
+    fn chips(amount: i64, scale: i64) -> Result<Chips, String> {
+        Chips::try_from(amount / scale)
+            .map_err(|_| format!("{} chips is too deep to replay", amount))
+    }
+    /// deal random cards to whoever we did not see
+    fn holes(&self) -> Vec<Hole> {
+        let known = self
+            .holes
+            .iter()
+            .flatten()
+            .map(|h| Hand::from(*h))
+            .chain(self.plays.iter().filter_map(|(_, _, raw)| match raw {
+                Raw::Exact(Action::Draw(cards)) => Some(*cards),
+                _ => None,
+            }))
+            .fold(Hand::empty(), Hand::add);
+        let mut deck = Deck::from(known.complement());
+        self.holes
+            .iter()
+            .map(|h| h.unwrap_or_else(|| deck.hole()))
+            .collect()
+    }
+}
+
+impl Draft {
+    /// keep every amount exactly as written
+    pub fn exact(self) -> Result<HandHistory, String> {
+        self.build(1)
+    }
+    fn build(self, scale: i64) -> Result<HandHistory, String> {
+        let draft = self;
+        if draft.stacks.len() != crate::N {
+            return Err(format!(
+                "{} players seated, but tables have {}",
+                draft.stacks.len(),
+                crate::N
+            ));
+        }
+        let seats = draft
+            .stacks
+            .iter()
+            .zip(draft.holes())
+            .enumerate()
+            .map(|(seat, (stack, hole))| {
+                Ok(Entry {
+                    seat,
+                    stack: Draft::chips(*stack, scale)?,
+                    hole,
+                })
+            })
+            .collect::<Result<Vec<Entry>, String>>()?;
+        let plays = draft
+            .plays
+            .iter()
+            .map(|(street, seat, raw)| {
+                Ok(Play {
+                    street: *street,
+                    seat: *seat,
+                    action: match *raw {
+                        Raw::Chips(action, amount) => action(Draft::chips(amount, scale)?),
+                        Raw::Exact(action) => action,
+                    },
+                })
+            })
+            .collect::<Result<Vec<Play>, String>>()?;
+        Ok(HandHistory {
+            id: draft.id,
+            time: draft.time,
+            dealer: draft.dealer,
+            blinds: (
+                Draft::chips(draft.blinds.0, scale)?,
+                Draft::chips(draft.blinds.1, scale)?,
+            ),
+            seats,
+            plays,
+            board: Board::empty(),
+            pots: Vec::new(),
+            results: Vec::new(),
+            rake: Rake::default(),
+        })
+    }
+}
+
+/// scale amounts down by their greatest common divisor
+impl TryFrom<Draft> for HandHistory {
+    type Error = String;
+    fn try_from(draft: Draft) -> Result<Self, Self::Error> {
+        let scale = draft.scale();
+        draft.build(scale)
+    }
+}
@@ -0,0 +1,223 @@
+use super::notation::Notation;
+use crate::gameplay::action::Action;
+use crate::gameplay::game::Game;
+
+/// every line played out from one root Game.
+///
+/// `Game` forgets how it got where it is, so we keep each Action along
+/// with the Game it led to, in a tree whose root is the starting Game.
+/// taking an Action we haven't tried from here opens a new branch and
+/// leaves the old ones where they were. undo walks back toward the root,
+/// and redo walks forward along whichever branch we were on last.
+#[derive(Debug, Clone)]
+pub struct GameHistory {
+    steps: Vec<Step>,
+    head: usize,
+}
+
+/// one node in the tree. the root has no Action and no parent.
+#[derive(Debug, Clone)]
+struct Step {
+    game: Game,
+    action: Option<Action>,
+    parent: Option<usize>,
+    next: Option<usize>,
+}
+
+impl From<Game> for GameHistory {
+    fn from(root: Game) -> Self {
+        Self {
+            steps: vec![Step {
+                game: root,
+                action: None,
+                parent: None,
+                next: None,
+            }],
+            head: 0,
+        }
+    }
+}
+
+impl GameHistory {
+    pub fn root(&self) -> &Game {
+        &self.steps[0].game
+    }
+    /// the Game at the current point in the current line
+    pub fn game(&self) -> &Game {
+        &self.steps[self.head].game
+    }
+    /// how many Actions in we are
+    pub fn depth(&self) -> usize {
+        self.path().len()
+    }
+    /// Actions from the root to where we are
+    pub fn line(&self) -> Vec<Action> {
+        self.path()
+            .into_iter()
+            .filter_map(|i| self.steps[i].action)
+            .collect()
+    }
+    /// Actions already tried from here, oldest first
+    pub fn branches(&self) -> Vec<Action> {
+        self.children(self.head)
+            .filter_map(|i| self.steps[i].action)
+            .collect()
+    }
+    /// every line that has been played out to its end
+    pub fn lines(&self) -> Vec<Vec<Action>> {
+        (0..self.steps.len())
+            .filter(|&i| self.children(i).next().is_none())
+            .filter(|&i| i != 0 || self.steps.len() == 1)
+            .map(|i| {
+                self.ancestors(i)
+                    .into_iter()
+                    .filter_map(|i| self.steps[i].action)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// take an Action from here, following an existing branch if we've
+    /// tried it before, and opening a new one if we haven't
+    pub fn apply(&mut self, action: Action) -> Result<(), String> {
+        self.game().validate(&action)?;
+        let tried = self
+            .children(self.head)
+            .find(|&i| self.steps[i].action == Some(action));
+        let step = match tried {
+            Some(i) => i,
+            None => {
+                self.steps.push(Step {
+                    game: self.game().apply(action),
+                    action: Some(action),
+                    parent: Some(self.head),
+                    next: None,
+                });
+                self.steps.len() - 1
+            }
+        };
+        self.steps[self.head].next = Some(step);
+        self.head = step;
+        Ok(())
+    }
+    /// step back one Action, if we aren't at the root
+    pub fn undo(&mut self) -> bool {
+        match self.steps[self.head].parent {
+            Some(parent) => {
+                self.steps[parent].next = Some(self.head);
+                self.head = parent;
+                true
+            }
+            None => false,
+        }
+    }
+    /// step forward along the branch we were on last
+    pub fn redo(&mut self) -> bool {
+        match self.steps[self.head].next {
+            Some(next) => {
+                self.head = next;
+                true
+            }
+            None => false,
+        }
+    }
+    /// go back to the point this many Actions into the current line,
+    /// keeping the rest of it around for redo
+    pub fn jump(&mut self, depth: usize) -> bool {
+        if depth > self.depth() {
+            return false;
+        }
+        while self.depth() > depth {
+            self.undo();
+        }
+        true
+    }
+
+    /// indices from the first Action down to the current step
+    fn path(&self) -> Vec<usize> {
+        self.ancestors(self.head)
+    }
+    fn ancestors(&self, mut i: usize) -> Vec<usize> {
+        let mut path = Vec::new();
+        while let Some(parent) = self.steps[i].parent {
+            path.push(i);
+            i = parent;
+        }
+        path.reverse();
+        path
+    }
+    fn children(&self, parent: usize) -> impl Iterator<Item = usize> + '_ {
+        (0..self.steps.len()).filter(move |&i| self.steps[i].parent == Some(parent))
+    }
+}
+
+/// the current line, written out
+impl From<&GameHistory> for Notation {
+    fn from(history: &GameHistory) -> Self {
+        Notation::from((*history.root(), history.line()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history() -> GameHistory {
+        let notation = Notation::try_from("BTN 100 BB 100 | AsKd QhQc").unwrap();
+        GameHistory::from(*notation.root())
+    }
+
+    #[test]
+    fn undo_redo() {
+        let mut history = history();
+        history.apply(Action::Raise(5)).unwrap();
+        history.apply(Action::Call(4)).unwrap();
+        let called = *history.game();
+        assert!(history.undo());
+        assert!(history.undo());
+        assert!(!history.undo());
+        assert!(history.game() == history.root());
+        assert!(history.redo());
+        assert!(history.redo());
+        assert!(!history.redo());
+        assert!(*history.game() == called);
+        assert!(history.line() == vec![Action::Raise(5), Action::Call(4)]);
+    }
+
+    #[test]
+    fn branches_are_kept() {
+        let mut history = history();
+        history.apply(Action::Raise(5)).unwrap();
+        history.apply(Action::Call(4)).unwrap();
+        assert!(history.jump(1));
+        history.apply(Action::Fold).unwrap();
+        assert!(history.game().pot() == 8);
+        assert!(history.undo());
+        assert!(history.branches() == vec![Action::Call(4), Action::Fold]);
+        assert!(history.redo());
+        assert!(history.line() == vec![Action::Raise(5), Action::Fold]);
+        assert!(history.lines().len() == 2);
+        history.undo();
+        history.apply(Action::Call(4)).unwrap();
+        assert!(history.steps.len() == 4);
+    }
+
+    #[test]
+    fn illegal_actions_are_rejected() {
+        let mut history = history();
+        assert!(history.apply(Action::Check).is_err());
+        assert!(history.apply(Action::Raise(1)).is_err());
+        assert!(history.depth() == 0);
+        assert!(!history.jump(1));
+    }
+
+    #[test]
+    fn writes_current_line() {
+        let mut history = history();
+        history.apply(Action::Raise(5)).unwrap();
+        history.apply(Action::Call(4)).unwrap();
+        history.undo();
+        let notation = Notation::from(&history).to_string();
+        assert!(notation == "BTN 100 BB 100 | KdAs QcQh | r6");
+    }
+}
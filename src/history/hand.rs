@@ -0,0 +1,303 @@
+use crate::cards::board::Board;
+use crate::cards::hand::Hand;
+use crate::cards::hole::Hole;
+use crate::cards::street::Street;
+use crate::cards::strength::Strength;
+use crate::gameplay::action::Action;
+use crate::gameplay::game::Game;
+use crate::gameplay::game::Position;
+use crate::gameplay::ply::Ply;
+use crate::gameplay::pot::Pot;
+use crate::gameplay::rake::Rake;
+use crate::gameplay::seat::State;
+use crate::gameplay::settlement::Settlement;
+use crate::Chips;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// a complete, self-contained record of one played hand.
+///
+/// `Game` forgets everything in between actions, so the table loop
+/// opens a `HandHistory` once hole cards are dealt and blinds are posted,
+/// records every `Action` as it is applied, and closes it with the
+/// `Settlement`s once the hand is terminal. each hand serializes to
+/// exactly one line of JSON, so a session is a `.jsonl` file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HandHistory {
+    pub id: usize,
+    #[serde(default)]
+    pub time: u64,
+    pub dealer: Position,
+    pub blinds: (Chips, Chips),
+    pub seats: Vec<Entry>,
+    pub plays: Vec<Play>,
+    pub board: Board,
+    pub pots: Vec<Pot>,
+    pub results: Vec<Outcome>,
+    #[serde(default)]
+    pub rake: Rake,
+}
+
+/// who sat where, with how many chips, holding which cards
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub seat: Position,
+    pub stack: Chips,
+    pub hole: Hole,
+}
+
+/// one Action, attributed to the seat that took it (None for chance)
+/// and the Street on which it was taken. board cards are recorded as
+/// Action::Draw on the Street that precedes their reveal.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Play {
+    pub street: Street,
+    pub seat: Option<Position>,
+    pub action: Action,
+}
+
+/// the serializable half of a Settlement. Strength is a pure function
+/// of hole cards and board, so we recompute rather than persist it.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Outcome {
+    pub seat: Position,
+    pub risked: Chips,
+    pub reward: Chips,
+    pub status: State,
+    #[serde(default)]
+    pub rake: Chips,
+}
+
+impl HandHistory {
+    /// append an Action that is about to be applied to this Game
+    pub fn record(&mut self, game: &Game, action: Action) {
+        let street = game.board().street();
+        let seat = match game.player() {
+            Ply::Choice(i) => Some(i),
+            Ply::Chance => None,
+            Ply::Terminal => panic!("no actions after terminal"),
+        };
+        self.plays.push(Play {
+            street,
+            seat,
+            action,
+        });
+    }
+    /// close the record with the results of a terminal Game
+    pub fn settle(&mut self, game: &Game) {
+        let settlements = game.settlements();
+        self.board = game.board();
+        self.pots = game.pots();
+        self.results = settlements
+            .iter()
+            .enumerate()
+            .map(|(seat, s)| Outcome {
+                seat,
+                risked: s.risked,
+                reward: s.reward,
+                status: s.status,
+                rake: s.rake,
+            })
+            .collect();
+    }
+    /// has this hand been settled yet?
+    pub fn is_complete(&self) -> bool {
+        !self.results.is_empty()
+    }
+    /// rebuild full Settlements, recomputing Strength from cards
+    pub fn settlements(&self) -> Vec<Settlement> {
+        self.results
+            .iter()
+            .zip(self.seats.iter())
+            .map(|(outcome, entry)| Settlement {
+                reward: outcome.reward,
+                risked: outcome.risked,
+                status: outcome.status,
+                rake: outcome.rake,
+                strength: Strength::from(Hand::add(Hand::from(entry.hole), Hand::from(self.board))),
+            })
+            .collect()
+    }
+    /// did this hand reach showdown between two or more players?
+    pub fn is_showdown(&self) -> bool {
+        self.results
+            .iter()
+            .filter(|o| o.status != State::Folding)
+            .count()
+            > 1
+    }
+    /// the leading plays that posted blinds, rather than decided anything
+    pub fn posted(&self) -> &[Play] {
+        let n = self
+            .plays
+            .iter()
+            .take(2)
+            .take_while(|p| p.street == Street::Pref)
+            .take_while(|p| matches!(p.action, Action::Blind(_) | Action::Shove(_)))
+            .count();
+        &self.plays[..n]
+    }
+    /// total chips that went into the middle
+    pub fn total(&self) -> Chips {
+        self.pots.iter().map(|p| p.amount).sum()
+    }
+    /// chips the house kept
+    pub fn raked(&self) -> Chips {
+        self.results.iter().map(|o| o.rake).sum()
+    }
+}
+
+/// open a new record at the first decision of a hand.
+/// blinds were already posted by the time we see the Game,
+/// so we reconstruct them from what each blind seat has spent.
+impl From<(usize, &Game)> for HandHistory {
+    fn from((id, game): (usize, &Game)) -> Self {
+        let dealer = game.dealer();
+        let seats = game
+            .seats()
+            .iter()
+            .enumerate()
+            .map(|(seat, s)| Entry {
+                seat,
+                stack: s.stack() + s.spent(),
+                hole: s.cards(),
+            })
+            .collect::<Vec<Entry>>();
+        let (small, big) = game.blinders();
+        let plays = [small, big]
+            .into_iter()
+            .map(|seat| (seat, game.seats()[seat]))
+            .filter(|(_, s)| s.spent() > 0)
+            .map(|(seat, s)| Play {
+                street: Street::Pref,
+                seat: Some(seat),
+                action: match s.state() {
+                    State::Shoving => Action::Shove(s.spent()),
+                    _ => Action::Blind(s.spent()),
+                },
+            })
+            .collect::<Vec<Play>>();
+        let time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("time moves slow")
+            .as_secs();
+        Self {
+            id,
+            time,
+            dealer,
+            blinds: (game.sblind(), game.bblind()),
+            seats,
+            plays,
+            board: Board::empty(),
+            pots: Vec::new(),
+            results: Vec::new(),
+            rake: game.rake(),
+        }
+    }
+}
+
+/// persistence methods
+impl HandHistory {
+    const FILE: &'static str = "hands.jsonl";
+    /// append this hand as a single line to the session file
+    pub fn save(&self) {
+        use std::io::Write;
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(Self::FILE)
+            .expect("open hand history file");
+        writeln!(file, "{}", self).expect("write hand history");
+    }
+    /// read back every hand recorded in the session file
+    pub fn load() -> Vec<Self> {
+        std::fs::read_to_string(Self::FILE)
+            .unwrap_or_default()
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| Self::try_from(line).expect("valid hand history line"))
+            .collect()
+    }
+}
+
+/// str isomorphism, one JSON object per line
+impl TryFrom<&str> for HandHistory {
+    type Error = String;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        serde_json::from_str(s.trim()).map_err(|e| e.to_string())
+    }
+}
+
+impl std::fmt::Display for HandHistory {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let json = serde_json::to_string(self).map_err(|_| std::fmt::Error)?;
+        write!(f, "{}", json)
+    }
+}
+
+/// test hands, played out from the root
+#[cfg(test)]
+impl HandHistory {
+    /// play a hand from Game::root() to the end, dealing random
+    /// cards and asking decide() for every seat's decisions
+    pub fn played(mut decide: impl FnMut(&Game) -> Action) -> Self {
+        let mut game = Game::root();
+        let mut hand = Self::from((0, &game));
+        while game.player() != Ply::Terminal {
+            let action = match game.player() {
+                Ply::Chance => Action::Draw(game.draw()),
+                _ => decide(&game),
+            };
+            hand.record(&game, action);
+            game = game.apply(action);
+        }
+        hand.settle(&game);
+        hand
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn played() -> HandHistory {
+        HandHistory::played(|game| {
+            game.legal()
+                .into_iter()
+                .find(|a| matches!(a, Action::Call(_) | Action::Check))
+                .expect("passive option")
+        })
+    }
+
+    #[test]
+    fn blinds_are_recorded() {
+        let game = Game::root();
+        let hand = HandHistory::from((0, &game));
+        assert!(hand.plays.len() == 2);
+        assert!(hand.plays[0].action == Action::Blind(game.sblind()));
+        assert!(hand.plays[1].action == Action::Blind(game.bblind()));
+        assert!(hand.seats.iter().all(|e| e.stack == crate::STACK));
+    }
+
+    #[test]
+    fn checked_down() {
+        let hand = played();
+        assert!(hand.is_complete());
+        assert!(hand.is_showdown());
+        assert!(hand.board.street() == Street::Rive);
+        assert!(hand.total() == 2 * hand.blinds.1);
+        assert!(hand.pots.len() == 1);
+        assert!(!hand.pots[0].winners.is_empty());
+        assert!(hand.plays.iter().filter(|p| p.seat.is_none()).count() == 3);
+        assert!(hand.results.iter().map(|o| o.reward).sum::<Chips>() == hand.total());
+        assert!(hand.settlements().iter().map(|s| s.pnl()).sum::<Chips>() == 0);
+    }
+
+    #[test]
+    fn bijective_json() {
+        let hand = played();
+        let line = hand.to_string();
+        assert!(!line.contains('\n'));
+        assert!(hand == HandHistory::try_from(line.as_str()).unwrap());
+    }
+}
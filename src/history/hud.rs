@@ -0,0 +1,420 @@
+use super::hand::HandHistory;
+use super::replay::Replay;
+use crate::cards::street::Street;
+use crate::gameplay::action::Action;
+use crate::gameplay::event::Event;
+use crate::gameplay::game::Game;
+use crate::gameplay::game::Position;
+use crate::gameplay::observer::Observer;
+use crate::gameplay::seat::State;
+use crate::N;
+
+/// how each seat plays, tallied over recorded hands.
+///
+/// every hand is replayed through the engine, so that each Action is
+/// read against the Game it was taken in: a shove can be a call or a
+/// raise, and only the engine knows which. Stats are kept per seat and
+/// per position, counted from the button, and summed on demand.
+#[derive(Debug, Clone)]
+pub struct Hud {
+    stats: Vec<Vec<Stats>>,
+}
+
+/// the usual HUD numbers for one seat. preflop raises are counted
+/// after the big blind, so an open is the 2-bet and a re-raise of
+/// it the 3-bet. everything after preflop is kept street by street.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Stats {
+    pub hands: usize,
+    /// net won, in big blinds
+    pub won: f64,
+    pub vpip: Ratio,
+    pub pfr: Ratio,
+    pub three_bet: Ratio,
+    pub fold_to_three_bet: Ratio,
+    /// went to showdown, out of hands that saw a flop
+    pub wtsd: Ratio,
+    /// won money, out of hands that went to showdown
+    pub wsd: Ratio,
+    pub streets: [Line; 4],
+}
+
+/// betting on one street. a continuation bet is a bet led by
+/// whoever made the last raise on the street before.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Line {
+    pub aggressive: usize,
+    pub calls: usize,
+    pub cbet: Ratio,
+    pub fold_to_cbet: Ratio,
+}
+
+/// how often something happened, out of how many chances it had
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Ratio {
+    pub hits: usize,
+    pub chances: usize,
+}
+
+impl Default for Hud {
+    fn default() -> Self {
+        Self {
+            stats: vec![vec![Stats::default(); N]; N],
+        }
+    }
+}
+
+impl From<&[HandHistory]> for Hud {
+    fn from(hands: &[HandHistory]) -> Self {
+        let mut hud = Self::default();
+        hands.iter().for_each(|hand| hud.record(hand));
+        hud
+    }
+}
+
+impl Hud {
+    /// tally one hand, unless the engine won't replay it
+    pub fn record(&mut self, hand: &HandHistory) {
+        match Replay::try_from(hand) {
+            Ok(replay) => {
+                for seat in 0..N {
+                    let position = (seat + N - hand.dealer) % N;
+                    self.stats[seat][position] += Stats::from((&replay, seat));
+                }
+            }
+            Err(illegal) => log::warn!("hand #{} left out of the HUD: {}", hand.id, illegal),
+        }
+    }
+    /// everything this seat did, from every position
+    pub fn seat(&self, seat: Position) -> Stats {
+        self.stats[seat]
+            .iter()
+            .cloned()
+            .fold(Stats::default(), |mut sum, stats| {
+                sum += stats;
+                sum
+            })
+    }
+    /// what this seat did from this many seats after the button
+    pub fn position(&self, seat: Position, position: Position) -> &Stats {
+        &self.stats[seat][position]
+    }
+
+    fn label(position: Position) -> String {
+        match position {
+            0 => "BTN".to_string(),
+            p if p == N - 1 => "BB".to_string(),
+            p if p == N - 2 => "SB".to_string(),
+            p => format!("P{}", p),
+        }
+    }
+}
+
+/// the HUD fills itself in from the hands a Table finishes
+impl Observer for Hud {
+    fn notify(&mut self, event: &Event) {
+        if let Event::Finished(hand) = event {
+            self.record(hand);
+        }
+    }
+}
+
+impl Stats {
+    pub fn bb100(&self) -> f64 {
+        match self.hands {
+            0 => 0.,
+            n => self.won / n as f64 * 100.,
+        }
+    }
+    /// bets and raises per call, after the flop
+    pub fn aggression(&self) -> Option<f64> {
+        let (aggressive, calls) = self.streets[1..]
+            .iter()
+            .fold((0, 0), |(a, c), line| (a + line.aggressive, c + line.calls));
+        (calls > 0).then(|| aggressive as f64 / calls as f64)
+    }
+    pub fn cbet(&self) -> Ratio {
+        self.streets[Street::Flop as usize].cbet
+    }
+    pub fn fold_to_cbet(&self) -> Ratio {
+        self.streets[Street::Flop as usize].fold_to_cbet
+    }
+
+    /// a raise, or a shove for more than it costs to call
+    fn is_aggressive(game: &Game, action: &Action) -> bool {
+        match action {
+            Action::Raise(_) => true,
+            Action::Shove(chips) => *chips > game.to_call(),
+            _ => false,
+        }
+    }
+}
+
+impl Line {
+    /// bets and raises per call
+    pub fn aggression(&self) -> Option<f64> {
+        (self.calls > 0).then(|| self.aggressive as f64 / self.calls as f64)
+    }
+}
+
+impl Ratio {
+    pub fn rate(&self) -> Option<f64> {
+        (self.chances > 0).then(|| self.hits as f64 / self.chances as f64)
+    }
+    fn count(&mut self, hit: bool) {
+        self.chances += 1;
+        self.hits += hit as usize;
+    }
+}
+
+/// one seat's Stats from one replayed hand
+impl From<(&Replay, Position)> for Stats {
+    fn from((replay, seat): (&Replay, Position)) -> Self {
+        let hand = replay.hand();
+        let outcome = hand.results[seat];
+        let mut stats = Self {
+            hands: 1,
+            won: (outcome.reward - outcome.risked) as f64 / hand.blinds.1 as f64,
+            ..Self::default()
+        };
+        let mut street = Street::Pref;
+        let mut raises = 0;
+        let mut opener = None;
+        let mut previous = None;
+        let mut aggressor = None;
+        let mut cbettor = None;
+        let mut opened = false;
+        let mut decided = false;
+        let mut vpip = false;
+        let mut pfr = false;
+        let mut folded = false;
+        for (play, game) in replay.spots() {
+            let Some(actor) = play.seat else { continue };
+            if play.street != street {
+                street = play.street;
+                previous = aggressor.take();
+                cbettor = None;
+                opened = false;
+            }
+            let action = play.action;
+            let aggressive = Self::is_aggressive(game, &action);
+            if actor == seat {
+                let line = &mut stats.streets[street as usize];
+                match street {
+                    Street::Pref => {
+                        decided = true;
+                        vpip |= matches!(
+                            action,
+                            Action::Call(_) | Action::Raise(_) | Action::Shove(_)
+                        );
+                        pfr |= aggressive;
+                        folded |= action == Action::Fold;
+                        if raises == 1 && opener != Some(seat) {
+                            stats.three_bet.count(aggressive);
+                        }
+                        if raises == 2 && opener == Some(seat) {
+                            stats.fold_to_three_bet.count(action == Action::Fold);
+                        }
+                    }
+                    _ => {
+                        if !opened && previous == Some(seat) {
+                            line.cbet.count(aggressive);
+                        }
+                        if cbettor.is_some_and(|c| c != seat) {
+                            line.fold_to_cbet.count(action == Action::Fold);
+                        }
+                    }
+                }
+                match action {
+                    _ if aggressive => line.aggressive += 1,
+                    Action::Call(_) | Action::Shove(_) => line.calls += 1,
+                    _ => {}
+                }
+            }
+            if aggressive {
+                if street == Street::Pref {
+                    raises += 1;
+                    opener = opener.or(Some(actor));
+                }
+                cbettor = (!opened && previous == Some(actor)).then_some(actor);
+                aggressor = Some(actor);
+                opened = true;
+            }
+        }
+        if decided {
+            stats.vpip.count(vpip);
+            stats.pfr.count(pfr);
+        }
+        if hand.board.street() != Street::Pref && !folded {
+            let showdown = hand.is_showdown() && outcome.status != State::Folding;
+            stats.wtsd.count(showdown);
+            if showdown {
+                stats.wsd.count(outcome.reward > 0);
+            }
+        }
+        stats
+    }
+}
+
+impl std::ops::AddAssign for Ratio {
+    fn add_assign(&mut self, other: Self) {
+        self.hits += other.hits;
+        self.chances += other.chances;
+    }
+}
+
+impl std::ops::AddAssign for Line {
+    fn add_assign(&mut self, other: Self) {
+        self.aggressive += other.aggressive;
+        self.calls += other.calls;
+        self.cbet += other.cbet;
+        self.fold_to_cbet += other.fold_to_cbet;
+    }
+}
+
+impl std::ops::AddAssign for Stats {
+    fn add_assign(&mut self, other: Self) {
+        self.hands += other.hands;
+        self.won += other.won;
+        self.vpip += other.vpip;
+        self.pfr += other.pfr;
+        self.three_bet += other.three_bet;
+        self.fold_to_three_bet += other.fold_to_three_bet;
+        self.wtsd += other.wtsd;
+        self.wsd += other.wsd;
+        for (line, other) in self.streets.iter_mut().zip(other.streets) {
+            *line += other;
+        }
+    }
+}
+
+impl std::fmt::Display for Ratio {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.rate() {
+            Some(rate) => write!(f, "{:.1}%", rate * 100.),
+            None => write!(f, "-"),
+        }
+    }
+}
+
+impl std::fmt::Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let aggression = self
+            .aggression()
+            .map_or("-".to_string(), |af| format!("{:.2}", af));
+        write!(
+            f,
+            "{:>6} hands {:>+8.1} bb/100  vpip {} pfr {} 3bet {} f3b {} cbet {} fcb {} wtsd {} w$sd {} af {}",
+            self.hands,
+            self.bb100(),
+            self.vpip,
+            self.pfr,
+            self.three_bet,
+            self.fold_to_three_bet,
+            self.cbet(),
+            self.fold_to_cbet(),
+            self.wtsd,
+            self.wsd,
+            aggression,
+        )
+    }
+}
+
+impl std::fmt::Display for Hud {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for seat in 0..N {
+            let stats = self.seat(seat);
+            writeln!(f, "P{} {}", seat, stats)?;
+            for position in 0..N {
+                writeln!(
+                    f,
+                    "  {:<4}{}",
+                    Self::label(position),
+                    self.position(seat, position)
+                )?;
+            }
+            for street in Street::all().iter().skip(1) {
+                let line = stats.streets[*street as usize];
+                let aggression = line
+                    .aggression()
+                    .map_or("-".to_string(), |af| format!("{:.2}", af));
+                writeln!(
+                    f,
+                    "  {:<6} af {} cbet {} fcb {}",
+                    street, aggression, line.cbet, line.fold_to_cbet
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gameplay::ply::Ply;
+    use crate::gameplay::table::Table;
+    use crate::players::player::Player;
+
+    /// play one hand from the button, each seat acting from a script
+    fn scripted(scripts: [Vec<Action>; N]) -> HandHistory {
+        let mut scripts = scripts.map(|script| script.into_iter());
+        HandHistory::played(|game| match game.player() {
+            Ply::Choice(seat) => scripts[seat].next().expect("scripted"),
+            _ => unreachable!("only asked to decide"),
+        })
+    }
+
+    #[test]
+    fn three_bet_and_fold() {
+        let hand = scripted([
+            vec![Action::Raise(5), Action::Fold],
+            vec![Action::Raise(16)],
+        ]);
+        let hud = Hud::from(std::slice::from_ref(&hand));
+        let (opener, three) = (hud.seat(0), hud.seat(1));
+        assert!(opener.vpip.rate() == Some(1.) && opener.pfr.rate() == Some(1.));
+        assert!(opener.fold_to_three_bet.rate() == Some(1.));
+        assert!(opener.three_bet.chances == 0);
+        assert!(three.three_bet.rate() == Some(1.));
+        assert!(opener.won == -3.);
+        assert!(opener.wtsd.chances == 0);
+        assert!(hud.position(1, N - 1).hands == 1);
+    }
+
+    #[test]
+    fn cbet_and_fold() {
+        let hand = scripted([
+            vec![Action::Raise(5), Action::Raise(6)],
+            vec![Action::Call(4), Action::Check, Action::Fold],
+        ]);
+        let mut hud = Hud::default();
+        hud.notify(&Event::Finished(hand));
+        let (bettor, folder) = (hud.seat(0), hud.seat(1));
+        assert!(bettor.cbet().rate() == Some(1.));
+        assert!(folder.fold_to_cbet().rate() == Some(1.));
+        assert!(folder.cbet().chances == 0);
+        assert!(folder.vpip.rate() == Some(1.) && folder.pfr.rate() == Some(0.));
+        assert!(bettor.streets[Street::Flop as usize].aggressive == 1);
+        assert!(folder.streets[Street::Pref as usize].calls == 1);
+        assert!(bettor.wtsd.rate() == Some(0.) && folder.wtsd.rate() == Some(0.));
+        assert!(bettor.aggression().is_none());
+    }
+
+    #[test]
+    fn showdowns_add_up() {
+        let mut table = Table::from(vec![
+            Box::new(crate::players::caller::Caller) as Box<dyn Player>,
+            Box::new(crate::players::caller::Caller) as Box<dyn Player>,
+        ]);
+        table.set_reload(Some(crate::STACK));
+        let hands = table.session(40);
+        let hud = Hud::from(hands.as_slice());
+        let stats = (0..N).map(|s| hud.seat(s)).collect::<Vec<Stats>>();
+        assert!(stats.iter().all(|s| s.hands == 40));
+        assert!(stats.iter().all(|s| s.wtsd.rate() == Some(1.)));
+        assert!(stats.iter().map(|s| s.wsd.hits).sum::<usize>() >= 40);
+        assert!(stats.iter().map(|s| s.won).sum::<f64>().abs() < 1e-9);
+        assert!(stats.iter().all(|s| s.pfr.hits == 0));
+    }
+}
@@ -0,0 +1,8 @@
+pub mod acpc;
+pub mod draft;
+pub mod game;
+pub mod hand;
+pub mod hud;
+pub mod notation;
+pub mod pokerstars;
+pub mod replay;
@@ -0,0 +1,286 @@
+use crate::cards::hand::Hand;
+use crate::cards::hole::Hole;
+use crate::gameplay::action::Action;
+use crate::gameplay::game::Game;
+use crate::gameplay::game::Position;
+use crate::mccfr::spot::Spot;
+use crate::Chips;
+use crate::N;
+
+/// a spot written out by hand, in one line:
+///
+/// `BTN 100 BB 100 | AsKd QhQc | r6 c | 7h8h9c | x b4`
+///
+/// starting stacks labeled by position, then hole cards in the same
+/// order, then betting and board cards in turn, street by street.
+/// bets are f(old), x (check), c(all), or rN / bN to raise or bet to
+/// N chips on this street. calling or raising off the whole stack is
+/// a shove. blinds other than the default go after the stacks, as in
+/// `BTN 100 BB 100 5/10`.
+#[derive(Debug, Clone)]
+pub struct Notation {
+    root: Game,
+    past: Vec<Action>,
+}
+
+impl From<(Game, Vec<Action>)> for Notation {
+    fn from((root, past): (Game, Vec<Action>)) -> Self {
+        Self { root, past }
+    }
+}
+
+impl From<Notation> for Spot {
+    fn from(notation: Notation) -> Self {
+        Spot::from((notation.root, notation.past))
+    }
+}
+
+impl Notation {
+    pub fn root(&self) -> &Game {
+        &self.root
+    }
+    pub fn past(&self) -> &[Action] {
+        &self.past
+    }
+    /// the Game after everything written so far
+    pub fn game(&self) -> Game {
+        self.past
+            .iter()
+            .fold(self.root, |game, action| game.apply(*action))
+    }
+
+    fn label(game: &Game, seat: Position) -> String {
+        let (small, big) = game.blinders();
+        if seat == game.dealer() {
+            "BTN".to_string()
+        } else if seat == big {
+            "BB".to_string()
+        } else if seat == small {
+            "SB".to_string()
+        } else {
+            format!("P{}", (seat + N - game.dealer()) % N)
+        }
+    }
+    /// labeled stacks, one per seat
+    fn stacks<'a>(tokens: &[&'a str]) -> Result<Vec<(&'a str, Chips)>, String> {
+        tokens
+            .chunks(2)
+            .map(|pair| match pair[1].parse::<Chips>() {
+                Ok(stack) if stack > 0 => Ok((pair[0], stack)),
+                _ => Err(format!("invalid stack: {}", pair[1])),
+            })
+            .collect()
+    }
+    /// small/big, or the default if they weren't written
+    fn blinds(token: Option<&str>) -> Result<(Chips, Chips), String> {
+        let invalid = || format!("invalid blinds: {}", token.unwrap_or_default());
+        match token.map(|t| t.split_once('/')) {
+            None => Ok((crate::S_BLIND, crate::B_BLIND)),
+            Some(Some((small, big))) => Ok((
+                small.parse().map_err(|_| invalid())?,
+                big.parse().map_err(|_| invalid())?,
+            )),
+            Some(None) => Err(invalid()),
+        }
+    }
+    fn holes(s: &str) -> Result<Vec<Hole>, String> {
+        let holes = s
+            .split_whitespace()
+            .map(Hole::try_from)
+            .collect::<Result<Vec<Hole>, String>>()?;
+        let cards = holes
+            .iter()
+            .map(|hole| u64::from(Hand::from(*hole)))
+            .fold(Hand::empty(), |cards, hole| {
+                Hand::from(u64::from(cards) | hole)
+            });
+        match holes.len() {
+            n if n != N => Err(format!("expected {} holes: {}", N, s)),
+            _ if cards.size() != 2 * N => Err(format!("holes share cards: {}", s)),
+            _ => Ok(holes),
+        }
+    }
+    /// one betting token, sized against the Game it is taken in
+    fn decode(game: &Game, token: &str) -> Result<Action, String> {
+        let shove = game.to_shove();
+        match (token.get(..1), token.get(1..)) {
+            (Some("f"), Some("")) => Ok(Action::Fold),
+            (Some("x"), Some("")) => Ok(Action::Check),
+            (Some("c"), Some("")) if game.to_call() >= shove => Ok(Action::Shove(shove)),
+            (Some("c"), Some("")) => Ok(Action::Call(game.to_call())),
+            (Some("r" | "b"), Some(to)) => {
+                let to = to
+                    .parse::<Chips>()
+                    .map_err(|_| format!("invalid bet: {}", token))?;
+                match to - game.actor().stake() {
+                    chips if chips >= shove => Ok(Action::Shove(shove)),
+                    chips => Ok(Action::Raise(chips)),
+                }
+            }
+            _ => Err(format!("unknown bet: {}", token)),
+        }
+    }
+    fn encode(game: &Game, action: &Action) -> String {
+        match action {
+            Action::Fold => "f".to_string(),
+            Action::Check => "x".to_string(),
+            Action::Call(_) => "c".to_string(),
+            Action::Shove(chips) if *chips <= game.to_call() => "c".to_string(),
+            Action::Raise(chips) | Action::Shove(chips) => {
+                format!("r{}", game.actor().stake() + chips)
+            }
+            Action::Draw(hand) => hand.to_string(),
+            Action::Blind(_) => unreachable!("blinds are posted before the root"),
+        }
+    }
+}
+
+/// str isomorphism, up to the order cards and tokens are written in
+impl TryFrom<&str> for Notation {
+    type Error = String;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let sections = s.split('|').map(str::trim).collect::<Vec<&str>>();
+        let (seats, holes, streets) = match sections.as_slice() {
+            [seats, holes, streets @ ..] => (*seats, *holes, streets),
+            _ => return Err(format!("expected stacks and holes: {}", s)),
+        };
+        let tokens = seats.split_whitespace().collect::<Vec<&str>>();
+        if tokens.len() < 2 * N || tokens.len() > 2 * N + 1 {
+            return Err(format!("expected {} labeled stacks: {}", N, seats));
+        }
+        let stacks = Self::stacks(&tokens[..2 * N])?;
+        let blinds = Self::blinds(tokens.get(2 * N).copied())?;
+        let holes = Self::holes(holes)?;
+        let dealer = stacks
+            .iter()
+            .position(|(label, _)| label.eq_ignore_ascii_case("BTN"))
+            .ok_or(format!("nobody has the button: {}", seats))?;
+        let mut seated = [(0, Hole::empty()); N];
+        for (seat, ((_, stack), hole)) in stacks.iter().zip(holes).enumerate() {
+            seated[seat] = (*stack, hole);
+        }
+        let root = Game::seated(seated, dealer, blinds);
+        for (seat, (label, _)) in stacks.iter().enumerate() {
+            let expected = Self::label(&root, seat);
+            if !label.eq_ignore_ascii_case(&expected) {
+                return Err(format!(
+                    "seat {} should be {}, not {}",
+                    seat, expected, label
+                ));
+            }
+        }
+        let mut game = root;
+        let mut past = Vec::new();
+        for (i, section) in streets.iter().enumerate() {
+            let actions = match i % 2 {
+                0 => section
+                    .split_whitespace()
+                    .map(|token| token.to_lowercase())
+                    .collect::<Vec<String>>(),
+                _ => vec![section.to_string()],
+            };
+            for token in actions {
+                let action = match i % 2 {
+                    0 => Self::decode(&game, &token)?,
+                    _ => Action::Draw(Hand::try_from(token.as_str())?),
+                };
+                game.validate(&action)
+                    .map_err(|e| format!("{} at {}", e, token))?;
+                game = game.apply(action);
+                past.push(action);
+            }
+        }
+        Ok(Self { root, past })
+    }
+}
+
+impl std::fmt::Display for Notation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let root = &self.root;
+        let seats = root
+            .seats()
+            .iter()
+            .enumerate()
+            .map(|(i, s)| format!("{} {}", Self::label(root, i), s.stack() + s.spent()))
+            .collect::<Vec<String>>()
+            .join(" ");
+        let holes = root
+            .seats()
+            .iter()
+            .map(|s| s.cards().to_string())
+            .collect::<Vec<String>>()
+            .join(" ");
+        write!(f, "{} ", seats)?;
+        if (root.sblind(), root.bblind()) != (crate::S_BLIND, crate::B_BLIND) {
+            write!(f, "{}/{} ", root.sblind(), root.bblind())?;
+        }
+        write!(f, "| {}", holes)?;
+        let mut streets = vec![Vec::new()];
+        let mut game = *root;
+        for action in self.past.iter() {
+            let token = Self::encode(&game, action);
+            match action {
+                Action::Draw(_) => streets.extend([vec![token], Vec::new()]),
+                _ => streets.last_mut().expect("current street").push(token),
+            }
+            game = game.apply(*action);
+        }
+        if streets.last().is_some_and(|street| street.is_empty()) {
+            streets.pop();
+        }
+        for street in streets {
+            write!(f, " | {}", street.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::street::Street;
+    use crate::gameplay::ply::Ply;
+
+    #[test]
+    fn parse_flop() {
+        let notation = Notation::try_from("BTN 100 BB 100 | AsKd QhQc | r6 c | 7h8h9c").unwrap();
+        let game = notation.game();
+        assert!(notation.past()[0] == Action::Raise(5));
+        assert!(notation.past()[1] == Action::Call(4));
+        assert!(game.board().street() == Street::Flop);
+        assert!(game.pot() == 12);
+        assert!(game.seats().iter().all(|s| s.stack() == 94));
+        assert!(game.player() == Ply::Choice(1));
+    }
+
+    #[test]
+    fn bijective_str() {
+        let text = "BB 60 BTN 100 5/10 | AsKd QhQc | r30 c | 7h8h9c | x b20 c";
+        let notation = Notation::try_from(text).unwrap();
+        let again = Notation::try_from(notation.to_string().as_str()).unwrap();
+        assert!(notation.game().dealer() == 1);
+        assert!(notation.game().bblind() == 10);
+        assert!(notation.past() == again.past());
+        assert!(notation.game() == again.game());
+    }
+
+    #[test]
+    fn covering_bets_are_shoves() {
+        let notation = Notation::try_from("BTN 50 BB 100 | AsKd QhQc | r100 c").unwrap();
+        assert!(notation.past()[0] == Action::Shove(49));
+        assert!(notation.past()[1] == Action::Call(48));
+        assert!(notation.to_string() == "BTN 50 BB 100 | KdAs QcQh | r50 c");
+        let notation = Notation::try_from("BTN 100 BB 50 | AsKd QhQc | r100 c").unwrap();
+        assert!(notation.past()[1] == Action::Shove(48));
+    }
+
+    #[test]
+    fn rejects_nonsense() {
+        assert!(Notation::try_from("BTN 100 BB 100 | AsKd").is_err());
+        assert!(Notation::try_from("BTN 100 BB 100 | AsKd AsQc").is_err());
+        assert!(Notation::try_from("BB 100 BB 100 | AsKd QhQc").is_err());
+        assert!(Notation::try_from("BTN 100 BB 100 | AsKd QhQc | r3").is_err());
+        assert!(Notation::try_from("BTN 100 BB 100 | AsKd QhQc | c x | As2c3c").is_err());
+        assert!(Notation::try_from("BTN 100 BB 100 | AsKd QhQc | c | 7h8h9c").is_err());
+    }
+}
@@ -0,0 +1,872 @@
+use super::draft::Draft;
+use super::draft::Raw;
+use super::hand::HandHistory;
+use crate::cards::board::Board;
+use crate::cards::hand::Hand;
+use crate::cards::hole::Hole;
+use crate::cards::rank::Rank;
+use crate::cards::ranking::Ranking;
+use crate::cards::street::Street;
+use crate::cards::strength::Strength;
+use crate::gameplay::action::Action;
+use crate::gameplay::game::Position;
+use crate::gameplay::seat::State;
+use crate::Chips;
+use std::fmt::Formatter;
+use std::fmt::Result;
+
+/// a HandHistory rendered in the PokerStars text format,
+/// which is what most trackers and replayers ingest.
+///
+/// chip amounts are written as plain integers, the same way
+/// PokerStars writes play-money and tournament hands. seats are
+/// 1-indexed and players are named P1, P2, ... by seat. every
+/// hole card is visible to us, so every seat gets a "Dealt to" line.
+pub struct PokerStars(HandHistory);
+
+impl From<HandHistory> for PokerStars {
+    fn from(hand: HandHistory) -> Self {
+        Self(hand)
+    }
+}
+impl From<PokerStars> for HandHistory {
+    fn from(stars: PokerStars) -> Self {
+        stars.0
+    }
+}
+
+impl PokerStars {
+    const TABLE: &'static str = "robopoker";
+
+    fn name(seat: Position) -> String {
+        format!("P{}", seat + 1)
+    }
+
+    /// the seats that posted the small and big blinds, in that order
+    fn blinds(&self) -> Vec<Position> {
+        self.0.posted().iter().filter_map(|p| p.seat).collect()
+    }
+
+    /// chips that nobody called, returned to the last aggressor
+    fn uncalled(&self) -> Option<(Position, Chips)> {
+        let mut risked = self
+            .0
+            .results
+            .iter()
+            .map(|o| (o.risked, o.seat))
+            .collect::<Vec<(Chips, Position)>>();
+        risked.sort_unstable_by(|a, b| b.cmp(a));
+        match risked.as_slice() {
+            [(most, seat), (next, _), ..] if most > next => Some((*seat, most - next)),
+            _ => None,
+        }
+    }
+
+    /// chips won from the pot, net of any uncalled bet returned
+    fn collected(&self, seat: Position) -> Chips {
+        let reward = self.0.results[seat].reward;
+        match self.uncalled() {
+            Some((s, chips)) if s == seat => reward - chips,
+            _ => reward,
+        }
+    }
+
+    /// total pot and its main/side breakdown, net of the uncalled bet
+    fn pots(&self) -> Vec<Chips> {
+        let mut pots = self.0.pots.iter().map(|p| p.amount).collect::<Vec<_>>();
+        if let (Some((_, chips)), Some(last)) = (self.uncalled(), pots.last_mut()) {
+            *last -= chips;
+        }
+        pots.into_iter().filter(|&p| p > 0).collect()
+    }
+
+    /// the Street on which a seat folded, if it did
+    fn folded(&self, seat: Position) -> Option<Street> {
+        self.0
+            .plays
+            .iter()
+            .find(|p| p.seat == Some(seat) && p.action == Action::Fold)
+            .map(|p| p.street)
+    }
+
+    fn strength(&self, seat: Position) -> Strength {
+        Strength::from(Hand::add(
+            Hand::from(self.0.seats[seat].hole),
+            Hand::from(self.0.board),
+        ))
+    }
+
+    /// time in YYYY/MM/DD HH:MM:SS, from unix seconds
+    fn date(&self) -> String {
+        let secs = self.0.time as i64;
+        let days = secs.div_euclid(86_400);
+        let time = secs.rem_euclid(86_400);
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let d = doy - (153 * mp + 2) / 5 + 1;
+        let m = if mp < 10 { mp + 3 } else { mp - 9 };
+        let y = yoe + era * 400 + if m <= 2 { 1 } else { 0 };
+        format!(
+            "{}/{:02}/{:02} {:02}:{:02}:{:02}",
+            y,
+            m,
+            d,
+            time / 3_600,
+            time % 3_600 / 60,
+            time % 60
+        )
+    }
+
+    fn header(&self, f: &mut Formatter) -> Result {
+        let hand = &self.0;
+        writeln!(
+            f,
+            "PokerStars Hand #{}:  Hold'em No Limit ({}/{}) - {} UTC",
+            hand.id,
+            hand.blinds.0,
+            hand.blinds.1,
+            self.date()
+        )?;
+        writeln!(
+            f,
+            "Table '{}' {}-max Seat #{} is the button",
+            Self::TABLE,
+            hand.seats.len(),
+            hand.dealer + 1
+        )?;
+        for entry in hand.seats.iter() {
+            writeln!(
+                f,
+                "Seat {}: {} ({} in chips)",
+                entry.seat + 1,
+                Self::name(entry.seat),
+                entry.stack
+            )?;
+        }
+        Ok(())
+    }
+
+    fn dealt(&self, f: &mut Formatter) -> Result {
+        writeln!(f, "*** HOLE CARDS ***")?;
+        for entry in self.0.seats.iter() {
+            writeln!(
+                f,
+                "Dealt to {} [{}]",
+                Self::name(entry.seat),
+                Self::cards(Hand::from(entry.hole))
+            )?;
+        }
+        Ok(())
+    }
+
+    /// replay the betting to turn chip deltas into bets, raises-to, and calls
+    fn actions(&self, f: &mut Formatter) -> Result {
+        let hand = &self.0;
+        let mut stakes = vec![0 as Chips; hand.seats.len()];
+        let mut board = Hand::empty();
+        let blinds = self.blinds().len();
+        let mut dealt = false;
+        for (i, play) in hand.plays.iter().enumerate() {
+            let is_blind = i < blinds;
+            if !is_blind && !dealt {
+                self.dealt(f)?;
+                dealt = true;
+            }
+            let seat = match (play.seat, play.action) {
+                (None, Action::Draw(cards)) => {
+                    let shown = Self::cards(board);
+                    let street = play.street.next();
+                    board = Hand::add(board, cards);
+                    stakes.iter_mut().for_each(|s| *s = 0);
+                    match street {
+                        Street::Flop => writeln!(f, "*** FLOP *** [{}]", Self::cards(board))?,
+                        Street::Turn => {
+                            writeln!(f, "*** TURN *** [{}] [{}]", shown, Self::cards(cards))?
+                        }
+                        Street::Rive => {
+                            writeln!(f, "*** RIVER *** [{}] [{}]", shown, Self::cards(cards))?
+                        }
+                        Street::Pref => unreachable!("nothing is dealt onto preflop"),
+                    }
+                    continue;
+                }
+                (Some(seat), _) => seat,
+                (None, action) => unreachable!("chance only draws, not {}", action),
+            };
+            let name = Self::name(seat);
+            if is_blind {
+                let chips = match play.action {
+                    Action::Blind(chips) | Action::Shove(chips) => chips,
+                    action => unreachable!("blinds are posted, not {}", action),
+                };
+                stakes[seat] += chips;
+                let which = if i == 0 { "small" } else { "big" };
+                let allin = if play.action.is_shove() {
+                    " and is all-in"
+                } else {
+                    ""
+                };
+                writeln!(f, "{}: posts {} blind {}{}", name, which, chips, allin)?;
+                continue;
+            }
+            match play.action {
+                Action::Fold => writeln!(f, "{}: folds", name)?,
+                Action::Check => writeln!(f, "{}: checks", name)?,
+                Action::Call(chips) => {
+                    stakes[seat] += chips;
+                    writeln!(f, "{}: calls {}", name, chips)?
+                }
+                Action::Raise(chips) | Action::Shove(chips) | Action::Blind(chips) => {
+                    let before = stakes.iter().copied().max().unwrap_or(0);
+                    stakes[seat] += chips;
+                    let after = stakes[seat];
+                    let allin = if play.action.is_shove() {
+                        " and is all-in"
+                    } else {
+                        ""
+                    };
+                    if after <= before {
+                        writeln!(f, "{}: calls {}{}", name, chips, allin)?
+                    } else if before == 0 {
+                        writeln!(f, "{}: bets {}{}", name, chips, allin)?
+                    } else {
+                        writeln!(
+                            f,
+                            "{}: raises {} to {}{}",
+                            name,
+                            after - before,
+                            after,
+                            allin
+                        )?
+                    }
+                }
+                Action::Draw(_) => unreachable!("players do not draw"),
+            }
+        }
+        if !dealt {
+            self.dealt(f)?;
+        }
+        Ok(())
+    }
+
+    fn showdown(&self, f: &mut Formatter) -> Result {
+        let hand = &self.0;
+        if let Some((seat, chips)) = self.uncalled() {
+            writeln!(
+                f,
+                "Uncalled bet ({}) returned to {}",
+                chips,
+                Self::name(seat)
+            )?;
+        }
+        if hand.is_showdown() {
+            writeln!(f, "*** SHOW DOWN ***")?;
+            for outcome in hand.results.iter().filter(|o| o.status != State::Folding) {
+                writeln!(
+                    f,
+                    "{}: shows [{}] ({})",
+                    Self::name(outcome.seat),
+                    Self::cards(Hand::from(hand.seats[outcome.seat].hole)),
+                    Self::describe(self.strength(outcome.seat))
+                )?;
+            }
+        }
+        for outcome in hand.results.iter() {
+            let collected = self.collected(outcome.seat);
+            if collected > 0 {
+                writeln!(
+                    f,
+                    "{} collected {} from pot",
+                    Self::name(outcome.seat),
+                    collected
+                )?;
+            }
+        }
+        if !hand.is_showdown() {
+            for outcome in hand.results.iter().filter(|o| o.status != State::Folding) {
+                writeln!(f, "{}: doesn't show hand", Self::name(outcome.seat))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn summary(&self, f: &mut Formatter) -> Result {
+        let hand = &self.0;
+        let pots = self.pots();
+        writeln!(f, "*** SUMMARY ***")?;
+        match pots.as_slice() {
+            [] | [_] => writeln!(
+                f,
+                "Total pot {} | Rake {}",
+                pots.iter().sum::<Chips>(),
+                hand.raked()
+            )?,
+            [main, sides @ ..] => writeln!(
+                f,
+                "Total pot {} Main pot {}. {} | Rake {}",
+                pots.iter().sum::<Chips>(),
+                main,
+                sides
+                    .iter()
+                    .enumerate()
+                    .map(|(i, side)| format!("Side pot-{} {}.", i + 1, side))
+                    .collect::<Vec<String>>()
+                    .join(" "),
+                hand.raked()
+            )?,
+        }
+        if hand.board != Board::empty() {
+            writeln!(f, "Board [{}]", Self::cards(Hand::from(hand.board)))?;
+        }
+        let blinds = self.blinds();
+        for outcome in hand.results.iter() {
+            let seat = outcome.seat;
+            let mut tags = String::new();
+            if seat == hand.dealer {
+                tags.push_str(" (button)");
+            }
+            if blinds.first() == Some(&seat) {
+                tags.push_str(" (small blind)");
+            }
+            if blinds.get(1) == Some(&seat) {
+                tags.push_str(" (big blind)");
+            }
+            let collected = self.collected(seat);
+            let result = match (self.folded(seat), hand.is_showdown()) {
+                (Some(Street::Pref), _) => "folded before Flop".to_string(),
+                (Some(street), _) => format!("folded on the {}", Self::street(street)),
+                (None, true) => format!(
+                    "showed [{}] and {} with {}",
+                    Self::cards(Hand::from(hand.seats[seat].hole)),
+                    match collected {
+                        0 => "lost".to_string(),
+                        n => format!("won ({})", n),
+                    },
+                    Self::describe(self.strength(seat))
+                ),
+                (None, false) => format!("collected ({})", collected),
+            };
+            writeln!(
+                f,
+                "Seat {}: {}{} {}",
+                seat + 1,
+                Self::name(seat),
+                tags,
+                result
+            )?;
+        }
+        Ok(())
+    }
+
+    fn cards(hand: Hand) -> String {
+        Board::from(hand).to_string()
+    }
+
+    fn street(street: Street) -> &'static str {
+        match street {
+            Street::Pref => "Preflop",
+            Street::Flop => "Flop",
+            Street::Turn => "Turn",
+            Street::Rive => "River",
+        }
+    }
+
+    /// the way PokerStars words a made hand at showdown
+    fn describe(strength: Strength) -> String {
+        match strength.ranking() {
+            Ranking::HighCard(r) => format!("high card {}", Self::singular(r)),
+            Ranking::OnePair(r) => format!("a pair of {}", Self::plural(r)),
+            Ranking::TwoPair(a, b) => {
+                format!("two pair, {} and {}", Self::plural(a), Self::plural(b))
+            }
+            Ranking::ThreeOAK(r) => format!("three of a kind, {}", Self::plural(r)),
+            Ranking::Straight(r) => format!("a straight, {} high", Self::singular(r)),
+            Ranking::Flush(r) => format!("a flush, {} high", Self::singular(r)),
+            Ranking::FullHouse(a, b) => format!(
+                "a full house, {} full of {}",
+                Self::plural(a),
+                Self::plural(b)
+            ),
+            Ranking::FourOAK(r) => format!("four of a kind, {}", Self::plural(r)),
+            Ranking::StraightFlush(Rank::Ace) => "a Royal Flush".to_string(),
+            Ranking::StraightFlush(r) => format!("a straight flush, {} high", Self::singular(r)),
+            Ranking::MAX => unreachable!(),
+        }
+    }
+    fn singular(rank: Rank) -> &'static str {
+        match rank {
+            Rank::Two => "Deuce",
+            Rank::Three => "Three",
+            Rank::Four => "Four",
+            Rank::Five => "Five",
+            Rank::Six => "Six",
+            Rank::Seven => "Seven",
+            Rank::Eight => "Eight",
+            Rank::Nine => "Nine",
+            Rank::Ten => "Ten",
+            Rank::Jack => "Jack",
+            Rank::Queen => "Queen",
+            Rank::King => "King",
+            Rank::Ace => "Ace",
+        }
+    }
+    fn plural(rank: Rank) -> &'static str {
+        match rank {
+            Rank::Two => "Deuces",
+            Rank::Three => "Threes",
+            Rank::Four => "Fours",
+            Rank::Five => "Fives",
+            Rank::Six => "Sixes",
+            Rank::Seven => "Sevens",
+            Rank::Eight => "Eights",
+            Rank::Nine => "Nines",
+            Rank::Ten => "Tens",
+            Rank::Jack => "Jacks",
+            Rank::Queen => "Queens",
+            Rank::King => "Kings",
+            Rank::Ace => "Aces",
+        }
+    }
+}
+
+/// parsing methods
+impl PokerStars {
+    /// cut a file of many hands into one str per hand
+    pub fn split(text: &str) -> Vec<&str> {
+        let mut starts = text
+            .match_indices("PokerStars ")
+            .map(|(i, _)| i)
+            .filter(|&i| {
+                text[..i].trim_start_matches('\u{feff}').is_empty() || text[..i].ends_with('\n')
+            })
+            .collect::<Vec<usize>>();
+        starts.push(text.len());
+        starts.windows(2).map(|w| &text[w[0]..w[1]]).collect()
+    }
+
+    /// amounts as written, in cents, ignoring currency and separators
+    fn amount(s: &str) -> std::result::Result<i64, String> {
+        let token = s
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .trim_start_matches(|c: char| !c.is_ascii_digit())
+            .trim_end_matches(|c: char| !c.is_ascii_digit())
+            .replace(',', "");
+        let (whole, frac) = token.split_once('.').unwrap_or((token.as_str(), ""));
+        let frac = format!("{:0<2}", frac);
+        match (whole.parse::<i64>(), frac[..2].parse::<i64>()) {
+            (Ok(whole), Ok(cents)) if frac.len() == 2 => Ok(whole * 100 + cents),
+            _ => Err(format!("invalid amount: {}", s)),
+        }
+    }
+
+    /// cards inside the last pair of brackets
+    fn bracketed(line: &str) -> std::result::Result<Hand, String> {
+        let open = line.rfind('[').ok_or(format!("no cards in: {}", line))?;
+        let close = line[open..]
+            .find(']')
+            .ok_or(format!("no cards in: {}", line))?;
+        Hand::try_from(&line[open + 1..open + close])
+    }
+
+    fn hole(line: &str) -> std::result::Result<Hole, String> {
+        match Self::bracketed(line)? {
+            hand if hand.size() == 2 => Ok(Hole::from(hand)),
+            _ => Err(format!("expected two hole cards in: {}", line)),
+        }
+    }
+
+    /// hand number, from "PokerStars Hand #123:"
+    fn id(line: &str) -> usize {
+        line.split_once('#')
+            .map(|(_, rest)| {
+                rest.chars()
+                    .take_while(|c| c.is_ascii_digit())
+                    .collect::<String>()
+            })
+            .and_then(|digits| digits.parse().ok())
+            .unwrap_or_default()
+    }
+
+    /// unix seconds, from the first YYYY/MM/DD HH:MM:SS in the header,
+    /// taken as UTC whatever the time zone says
+    fn time(line: &str) -> u64 {
+        let tokens = line
+            .split(|c: char| c.is_whitespace() || c == '[')
+            .collect::<Vec<&str>>();
+        tokens
+            .windows(2)
+            .find_map(|w| {
+                let date = w[0]
+                    .split('/')
+                    .map(|x| x.parse::<i64>().ok())
+                    .collect::<Option<Vec<i64>>>()?;
+                let time = w[1]
+                    .split(':')
+                    .map(|x| x.parse::<i64>().ok())
+                    .collect::<Option<Vec<i64>>>()?;
+                match (date.as_slice(), time.as_slice()) {
+                    ([y, m, d], [hh, mm, ss]) => {
+                        let y = if *m <= 2 { y - 1 } else { *y };
+                        let era = y.div_euclid(400);
+                        let yoe = y - era * 400;
+                        let mp = (m + 9) % 12;
+                        let doy = (153 * mp + 2) / 5 + d - 1;
+                        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+                        let days = era * 146_097 + doe - 719_468;
+                        u64::try_from(days * 86_400 + hh * 3_600 + mm * 60 + ss).ok()
+                    }
+                    _ => None,
+                }
+            })
+            .unwrap_or_default()
+    }
+
+    /// "Seat 3: name (1,000 in chips)", or None if sitting out.
+    /// whatever follows the chip count, like a bounty, is ignored.
+    fn seat(line: &str) -> Option<std::result::Result<(usize, String, i64), String>> {
+        if line.contains("sitting out") {
+            return None;
+        }
+        let invalid = || format!("invalid seat: {}", line);
+        let seat = || {
+            let (number, rest) = line.strip_prefix("Seat ")?.split_once(": ")?;
+            let chips = rest.rfind(" in chips")?;
+            let open = rest[..chips].rfind(" (")?;
+            let number = number.parse::<usize>().ok()?;
+            Some((number, &rest[..open], &rest[open + 2..chips]))
+        };
+        Some(
+            seat()
+                .ok_or_else(invalid)
+                .and_then(|(n, name, chips)| Ok((n, name.to_string(), Self::amount(chips)?))),
+        )
+    }
+
+    /// which seated player is speaking, and what they said
+    fn speaker<'a>(names: &[(usize, String, i64)], line: &'a str) -> Option<(Position, &'a str)> {
+        names
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (_, name, _))| {
+                line.strip_prefix(name.as_str())
+                    .and_then(|rest| rest.strip_prefix(": "))
+                    .map(|rest| (i, name.len(), rest))
+            })
+            .max_by_key(|(_, len, _)| *len)
+            .map(|(i, _, rest)| (i, rest))
+    }
+}
+
+/// str isomorphism, up to the holes we never saw and the scale of chips.
+///
+/// amounts may carry currency symbols and decimals, and are scaled
+/// down to Chips all together. antes and dead blinds have no place
+/// in our engine, so hands with them are refused.
+impl TryFrom<&str> for PokerStars {
+    type Error = String;
+    fn try_from(text: &str) -> std::result::Result<Self, Self::Error> {
+        let mut draft = Draft::default();
+        let mut names = Vec::<(usize, String, i64)>::new();
+        let mut holes = Vec::<(Position, Hole)>::new();
+        let mut stakes = Vec::<i64>::new();
+        let mut button = 0;
+        let mut street = Street::Pref;
+        let mut dealt = false;
+        let mut summary = false;
+        for line in text
+            .lines()
+            .map(|l| l.trim().trim_start_matches('\u{feff}'))
+        {
+            if line.starts_with("PokerStars ") {
+                draft.id = Self::id(line);
+                draft.time = Self::time(line);
+            } else if let Some(rest) = line.strip_prefix("Table ") {
+                button = rest
+                    .split_once("Seat #")
+                    .and_then(|(_, n)| n.split_whitespace().next())
+                    .and_then(|n| n.parse::<usize>().ok())
+                    .ok_or(format!("no button in: {}", line))?;
+            } else if line.starts_with("*** ") {
+                if line.starts_with("*** SUMMARY") {
+                    summary = true;
+                }
+                if line.starts_with("*** FLOP")
+                    || line.starts_with("*** TURN")
+                    || line.starts_with("*** RIVER")
+                {
+                    let cards = Self::bracketed(line)?;
+                    draft
+                        .plays
+                        .push((street, None, Raw::Exact(Action::Draw(cards))));
+                    street = street.next();
+                    stakes.iter_mut().for_each(|s| *s = 0);
+                }
+                dealt = true;
+            } else if line.starts_with("Seat ") && !dealt {
+                if let Some(seat) = Self::seat(line) {
+                    names.push(seat?);
+                    stakes.push(0);
+                }
+            } else if line.starts_with("Seat ") && summary {
+                if line.contains("showed [") || line.contains("mucked [") {
+                    let number = line["Seat ".len()..]
+                        .split(':')
+                        .next()
+                        .and_then(|n| n.parse::<usize>().ok());
+                    if let Some(i) = names.iter().position(|(n, _, _)| Some(*n) == number) {
+                        holes.push((i, Self::hole(line)?));
+                    }
+                }
+            } else if let Some(rest) = line.strip_prefix("Dealt to ") {
+                if let Some(i) = names.iter().position(|(_, name, _)| {
+                    rest.strip_prefix(name.as_str())
+                        .is_some_and(|r| r.starts_with(" ["))
+                }) {
+                    holes.push((i, Self::hole(line)?));
+                }
+            } else if let Some((seat, said)) = Self::speaker(&names, line) {
+                let allin = said.ends_with("and is all-in");
+                let play = |chips: fn(Chips) -> Action, amount: i64| {
+                    let chips = if allin { Action::Shove } else { chips };
+                    (street, Some(seat), Raw::Chips(chips, amount))
+                };
+                if let Some(rest) = said.strip_prefix("posts small blind ") {
+                    draft.blinds.0 = Self::amount(rest)?;
+                    stakes[seat] += draft.blinds.0;
+                    draft.plays.push(play(Action::Blind, draft.blinds.0));
+                } else if let Some(rest) = said.strip_prefix("posts big blind ") {
+                    draft.blinds.1 = Self::amount(rest)?;
+                    stakes[seat] += draft.blinds.1;
+                    draft.plays.push(play(Action::Blind, draft.blinds.1));
+                } else if said.starts_with("posts") {
+                    return Err(format!("unsupported post: {}", line));
+                } else if said.starts_with("folds") {
+                    draft
+                        .plays
+                        .push((street, Some(seat), Raw::Exact(Action::Fold)));
+                } else if said.starts_with("checks") {
+                    draft
+                        .plays
+                        .push((street, Some(seat), Raw::Exact(Action::Check)));
+                } else if let Some(rest) = said.strip_prefix("calls ") {
+                    let chips = Self::amount(rest)?;
+                    stakes[seat] += chips;
+                    draft.plays.push(play(Action::Call, chips));
+                } else if let Some(rest) = said.strip_prefix("bets ") {
+                    let chips = Self::amount(rest)?;
+                    stakes[seat] += chips;
+                    draft.plays.push(play(Action::Raise, chips));
+                } else if let Some(rest) = said.strip_prefix("raises ") {
+                    let to = rest
+                        .split_once(" to ")
+                        .map(|(_, to)| to)
+                        .ok_or(format!("invalid raise: {}", line))?;
+                    let to = Self::amount(to)?;
+                    let chips = to - stakes[seat];
+                    stakes[seat] = to;
+                    draft.plays.push(play(Action::Raise, chips));
+                } else if said.starts_with("shows [") {
+                    holes.push((seat, Self::hole(said)?));
+                }
+            }
+        }
+        if draft.blinds.0 == 0 || draft.blinds.1 == 0 {
+            return Err("both blinds must be posted".to_string());
+        }
+        draft.dealer = names
+            .iter()
+            .position(|(n, _, _)| *n == button)
+            .ok_or(format!("button on empty seat {}", button))?;
+        draft.stacks = names.iter().map(|(_, _, stack)| *stack).collect();
+        draft.holes = vec![None; names.len()];
+        for (seat, hole) in holes {
+            draft.holes[seat] = Some(hole);
+        }
+        HandHistory::try_from(draft).map(Self)
+    }
+}
+
+impl std::fmt::Display for PokerStars {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        self.header(f)?;
+        self.actions(f)?;
+        self.showdown(f)?;
+        self.summary(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gameplay::pot::Pot;
+    use crate::gameplay::rake::Rake;
+    use crate::history::hand::Entry;
+    use crate::history::hand::Outcome;
+    use crate::history::hand::Play;
+    use crate::history::replay::Replay;
+
+    fn play(street: Street, seat: Option<Position>, action: &str) -> Play {
+        Play {
+            street,
+            seat,
+            action: Action::try_from(action).unwrap(),
+        }
+    }
+
+    /// P2 limps, P1 checks, P1 checks the flop, P2 bets, P1 raises, P2 folds
+    /// P2 has the button, so posts the small blind and acts last after the flop
+    fn folded() -> HandHistory {
+        HandHistory {
+            id: 7,
+            time: 0,
+            dealer: 1,
+            blinds: (1, 2),
+            seats: vec![
+                Entry {
+                    seat: 0,
+                    stack: 100,
+                    hole: Hole::try_from("As Kd").unwrap(),
+                },
+                Entry {
+                    seat: 1,
+                    stack: 100,
+                    hole: Hole::try_from("7c 2d").unwrap(),
+                },
+            ],
+            plays: vec![
+                play(Street::Pref, Some(1), "BLIND 1"),
+                play(Street::Pref, Some(0), "BLIND 2"),
+                play(Street::Pref, Some(1), "CALL 1"),
+                play(Street::Pref, Some(0), "CHECK"),
+                play(Street::Pref, None, "DEAL 7h 8h 9c"),
+                play(Street::Flop, Some(0), "CHECK"),
+                play(Street::Flop, Some(1), "RAISE 2"),
+                play(Street::Flop, Some(0), "RAISE 8"),
+                play(Street::Flop, Some(1), "FOLD"),
+            ],
+            board: Board::try_from("7h 8h 9c").unwrap(),
+            pots: vec![Pot {
+                amount: 14,
+                eligible: vec![0],
+                winners: vec![0],
+            }],
+            results: vec![
+                Outcome {
+                    seat: 0,
+                    risked: 10,
+                    reward: 14,
+                    status: State::Betting,
+                    rake: 0,
+                },
+                Outcome {
+                    seat: 1,
+                    risked: 4,
+                    reward: 0,
+                    status: State::Folding,
+                    rake: 0,
+                },
+            ],
+            rake: Rake::default(),
+        }
+    }
+
+    #[test]
+    fn export_folded_hand() {
+        let text = PokerStars::from(folded()).to_string();
+        let lines = text.lines().collect::<Vec<&str>>();
+        assert!(
+            lines[0] == "PokerStars Hand #7:  Hold'em No Limit (1/2) - 1970/01/01 00:00:00 UTC"
+        );
+        assert!(lines[1] == "Table 'robopoker' 2-max Seat #2 is the button");
+        assert!(lines.contains(&"P2: posts small blind 1"));
+        assert!(lines.contains(&"P1: posts big blind 2"));
+        assert!(lines.contains(&"Dealt to P1 [Kd As]"));
+        assert!(lines.contains(&"*** FLOP *** [7h 8h 9c]"));
+        assert!(lines.contains(&"P2: bets 2"));
+        assert!(lines.contains(&"P1: raises 6 to 8"));
+        assert!(lines.contains(&"Uncalled bet (6) returned to P1"));
+        assert!(lines.contains(&"P1 collected 8 from pot"));
+        assert!(lines.contains(&"Total pot 8 | Rake 0"));
+        assert!(lines.contains(&"Seat 1: P1 (big blind) collected (8)"));
+        assert!(lines.contains(&"Seat 2: P2 (button) (small blind) folded on the Flop"));
+    }
+
+    #[test]
+    fn import_folded_hand() {
+        let hand = folded();
+        let text = PokerStars::from(hand.clone()).to_string();
+        let imported = HandHistory::from(PokerStars::try_from(text.as_str()).unwrap());
+        assert!(imported.seats == hand.seats);
+        assert!(imported.plays == hand.plays);
+        let replay = Replay::try_from(&imported).unwrap();
+        assert!(replay.hand().results == hand.results);
+    }
+
+    #[test]
+    fn import_cents() {
+        let text =
+            "PokerStars Hand #42: Hold'em No Limit ($0.50/$1.00 USD) - 2023/11/14 22:13:20 ET
+Table 'Alpha' 6-max Seat #4 is the button
+Seat 1: villain ($100 in chips)
+Seat 4: hero: the great ($98.50 in chips)
+hero: the great: posts small blind $0.50
+villain: posts big blind $1
+*** HOLE CARDS ***
+Dealt to hero: the great [Ah Ad]
+hero: the great: raises $2 to $3
+villain: raises $97 to $100 and is all-in
+hero: the great: calls $95.50 and is all-in
+*** FLOP *** [2c 7d 9h]
+*** TURN *** [2c 7d 9h] [Ts]
+*** RIVER *** [2c 7d 9h Ts] [3s]
+*** SHOW DOWN ***
+villain: shows [Kc Kd] (a pair of Kings)
+";
+        let hand = HandHistory::from(PokerStars::try_from(text).unwrap());
+        assert!(hand.id == 42);
+        assert!(hand.time == 1_700_000_000);
+        assert!(hand.dealer == 1);
+        assert!(hand.blinds == (1, 2));
+        assert!(hand.seats[1].stack == 197);
+        assert!(hand.seats[0].hole == Hole::try_from("Kc Kd").unwrap());
+        let replay = Replay::try_from(&hand).unwrap();
+        assert!(replay.hand().results[1].reward == 2 * 197);
+    }
+
+    #[test]
+    fn import_bounty_seats() {
+        let text = PokerStars::from(folded())
+            .to_string()
+            .replace("(100 in chips)", "(100 in chips, $10 bounty)");
+        assert!(text.contains("bounty"));
+        let hand = HandHistory::from(PokerStars::try_from(text.as_str()).unwrap());
+        assert!(hand.seats == folded().seats);
+        let text = text.replacen("(100 in chips, $10 bounty)", "(a hundred chips)", 1);
+        let error = PokerStars::try_from(text.as_str()).err().unwrap();
+        assert!(error.contains("(a hundred chips)"));
+    }
+
+    #[test]
+    fn import_illegal_raise() {
+        let text = PokerStars::from(folded())
+            .to_string()
+            .replace("P1: raises 6 to 8", "P1: raises 1 to 3");
+        let hand = HandHistory::from(PokerStars::try_from(text.as_str()).unwrap());
+        let illegal = Replay::try_from(&hand).unwrap_err();
+        assert!(illegal.index == 7);
+        assert!(illegal.reason.starts_with("raise must be"));
+    }
+
+    #[test]
+    fn dates() {
+        let mut hand = folded();
+        hand.time = 1_700_000_000;
+        assert!(PokerStars::from(hand).date() == "2023/11/14 22:13:20");
+    }
+}
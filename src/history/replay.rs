@@ -0,0 +1,266 @@
+use super::hand::HandHistory;
+use super::hand::Play;
+use crate::cards::hole::Hole;
+use crate::gameplay::game::Game;
+use crate::gameplay::ply::Ply;
+use crate::Chips;
+use crate::Utility;
+use crate::N;
+
+/// a HandHistory pushed back through the engine, one Action at a time.
+///
+/// this is how we check that imported hands obey our rules, and how
+/// we recover the actual Game at every decision point of a real hand,
+/// e.g. to look it up in the blueprint.
+#[derive(Debug, Clone)]
+pub struct Replay {
+    hand: HandHistory,
+    games: Vec<Game>,
+}
+
+/// the first recorded Action that the engine refused, and why
+#[derive(Debug, Clone, PartialEq)]
+pub struct Illegal {
+    pub index: usize,
+    pub play: Option<Play>,
+    pub reason: String,
+}
+
+impl Replay {
+    /// every Game state, starting from the first decision
+    pub fn games(&self) -> &[Game] {
+        &self.games
+    }
+    /// the Game state right before each recorded decision or draw
+    pub fn spots(&self) -> impl Iterator<Item = (&Play, &Game)> {
+        let posted = self.hand.posted().len();
+        self.hand.plays[posted..].iter().zip(self.games.iter())
+    }
+    /// the hand as the engine saw it, settled if it ran to completion
+    pub fn hand(&self) -> &HandHistory {
+        &self.hand
+    }
+    pub fn is_complete(&self) -> bool {
+        self.hand.is_complete()
+    }
+
+    /// each seat's net result, all-in adjusted. when the last decision
+    /// came before the river with two or more hands still live, the rest
+    /// of the board is run out every way it could have come from there,
+    /// with the actual side pots. every other hand counts as it settled.
+    pub fn adjusted(&self) -> Vec<Utility> {
+        let rewards = match self.closed() {
+            Some(game) if self.hand.is_showdown() => game.expected(),
+            _ => self
+                .hand
+                .results
+                .iter()
+                .map(|o| o.reward as Utility)
+                .collect(),
+        };
+        rewards
+            .into_iter()
+            .zip(self.hand.results.iter())
+            .map(|(reward, o)| reward - o.risked as Utility)
+            .collect()
+    }
+    /// the Game after the last decision, if only cards were dealt after it
+    fn closed(&self) -> Option<&Game> {
+        let drawn = self
+            .hand
+            .plays
+            .iter()
+            .rev()
+            .take_while(|p| p.seat.is_none())
+            .count();
+        match drawn {
+            0 => None,
+            n if self.is_complete() => self.games.iter().rev().nth(n),
+            _ => None,
+        }
+    }
+
+    fn start(hand: &HandHistory) -> Result<Game, Illegal> {
+        let refuse = |reason: String| Illegal {
+            index: 0,
+            play: None,
+            reason,
+        };
+        if hand.seats.len() != N {
+            return Err(refuse(format!(
+                "{} seats, expected {}",
+                hand.seats.len(),
+                N
+            )));
+        }
+        let (small, big) = hand.blinds;
+        if hand.seats.iter().any(|e| e.stack <= 0) {
+            return Err(refuse("every seat needs chips".to_string()));
+        }
+        let mut seats = [(0 as Chips, Hole::empty()); N];
+        for (seat, entry) in seats.iter_mut().zip(hand.seats.iter()) {
+            *seat = (entry.stack, entry.hole);
+        }
+        let mut game = Game::seated(seats, hand.dealer % N, (small, big));
+        game.set_rake(hand.rake);
+        let posted = HandHistory::from((hand.id, &game));
+        for (index, (expected, play)) in posted.plays.iter().zip(hand.posted()).enumerate() {
+            if expected != play {
+                return Err(Illegal {
+                    index,
+                    play: Some(*play),
+                    reason: format!("expected {}", Self::describe(expected)),
+                });
+            }
+        }
+        if posted.plays.len() != hand.posted().len() {
+            return Err(refuse("both blinds must be posted".to_string()));
+        }
+        Ok(game)
+    }
+
+    fn describe(play: &Play) -> String {
+        match play.seat {
+            Some(seat) => format!("seat {} {}", seat, String::from(play.action)),
+            None => format!("chance {}", String::from(play.action)),
+        }
+    }
+}
+
+/// a hand the engine just played, with every Game state it went
+/// through, so there's nothing to check by replaying it again
+impl From<(HandHistory, Vec<Game>)> for Replay {
+    fn from((hand, games): (HandHistory, Vec<Game>)) -> Self {
+        Self { hand, games }
+    }
+}
+
+/// the hand, as recorded
+impl From<Replay> for HandHistory {
+    fn from(replay: Replay) -> Self {
+        replay.hand
+    }
+}
+
+/// replay the hand, stopping at the first Action the engine refuses.
+/// hands that were already settled must settle the same way again.
+impl TryFrom<&HandHistory> for Replay {
+    type Error = Illegal;
+    fn try_from(hand: &HandHistory) -> Result<Self, Self::Error> {
+        let mut game = Self::start(hand)?;
+        let mut games = vec![game];
+        let posted = hand.posted().len();
+        for (index, play) in hand.plays.iter().enumerate().skip(posted) {
+            let refuse = |reason: String| Illegal {
+                index,
+                play: Some(*play),
+                reason,
+            };
+            match (game.player(), play.seat) {
+                (Ply::Choice(actor), Some(seat)) if actor == seat => {}
+                (Ply::Chance, None) => {}
+                (Ply::Choice(actor), _) => return Err(refuse(format!("seat {} to act", actor))),
+                (Ply::Chance, _) => return Err(refuse("cards to be dealt".to_string())),
+                (Ply::Terminal, _) => return Err(refuse("hand is already over".to_string())),
+            }
+            game.validate(&play.action).map_err(refuse)?;
+            game = game.apply(play.action);
+            games.push(game);
+        }
+        let mut replay = Self {
+            hand: hand.clone(),
+            games,
+        };
+        if game.player() == Ply::Terminal {
+            replay.hand.settle(&game);
+            if hand.is_complete() && hand.results != replay.hand.results {
+                return Err(Illegal {
+                    index: hand.plays.len(),
+                    play: None,
+                    reason: "recorded results differ from the engine's".to_string(),
+                });
+            }
+        }
+        Ok(replay)
+    }
+}
+
+impl std::fmt::Display for Illegal {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.play {
+            Some(ref play) => write!(
+                f,
+                "illegal play #{} ({} on {}): {}",
+                self.index,
+                Replay::describe(play),
+                play.street,
+                self.reason
+            ),
+            None => write!(f, "illegal hand: {}", self.reason),
+        }
+    }
+}
+
+impl std::error::Error for Illegal {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gameplay::action::Action;
+
+    fn played() -> HandHistory {
+        HandHistory::played(|game| game.legal().into_iter().next().expect("some option"))
+    }
+
+    #[test]
+    fn replay_played() {
+        let hand = played();
+        let replay = Replay::try_from(&hand).unwrap();
+        assert!(replay.hand() == &hand);
+        assert!(replay.games().len() == hand.plays.len() - hand.posted().len() + 1);
+        assert!(replay
+            .spots()
+            .all(|(play, game)| game.validate(&play.action).is_ok()));
+    }
+
+    #[test]
+    fn adjusted_all_in() {
+        let hand = HandHistory::played(|game| match game.to_call() {
+            0 => Action::Check,
+            n => Action::Call(n),
+        });
+        let settled = hand
+            .settlements()
+            .iter()
+            .map(|s| s.pnl() as Utility)
+            .collect::<Vec<_>>();
+        assert!(Replay::try_from(&hand).unwrap().adjusted() == settled);
+        let hand = HandHistory::played(|game| Action::Shove(game.to_shove()));
+        let adjusted = Replay::try_from(&hand).unwrap().adjusted();
+        assert!(adjusted.iter().sum::<Utility>().abs() < 1e-3);
+        assert!(adjusted.iter().all(|&pnl| pnl > -100. && pnl < 100.));
+    }
+
+    #[test]
+    fn illegal_call() {
+        let mut hand = played();
+        let index = hand
+            .plays
+            .iter()
+            .position(|p| matches!(p.action, Action::Call(_)))
+            .unwrap_or(hand.posted().len());
+        hand.plays[index].action = Action::Call(99);
+        let illegal = Replay::try_from(&hand).unwrap_err();
+        assert!(illegal.index == index);
+    }
+
+    #[test]
+    fn out_of_turn() {
+        let mut hand = played();
+        let index = hand.posted().len();
+        hand.plays[index].seat = hand.plays[index].seat.map(|s| (s + 1) % N);
+        let illegal = Replay::try_from(&hand).unwrap_err();
+        assert!(illegal.index == index);
+        assert!(illegal.reason.contains("to act"));
+    }
+}
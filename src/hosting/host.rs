@@ -0,0 +1,294 @@
+use super::protocol::Command;
+use super::protocol::Update;
+use super::remote::Remote;
+use crate::gameplay::action::Action;
+use crate::gameplay::arena::Entrant;
+use crate::gameplay::game::Position;
+use crate::gameplay::table::Table;
+use crate::players::caller::Caller;
+use crate::players::clock::Clock;
+use crate::players::player::Player;
+use crate::players::raiser::Raiser;
+use crate::players::random::Random;
+use crate::players::tag::Tag;
+use crate::players::threshold::Threshold;
+use crate::N;
+use axum::extract::ws::Message;
+use axum::extract::ws::WebSocket;
+use axum::extract::State;
+use axum::extract::WebSocketUpgrade;
+use axum::response::Response;
+use axum::routing::get;
+use axum::Json;
+use axum::Router;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::net::ToSocketAddrs;
+
+/// hosts Tables for people to play against bots, over HTTP and WebSocket.
+///
+/// `GET /bots` lists who there is to play against, and `GET /ws` opens a
+/// WebSocket that speaks the JSON Commands and Updates of our protocol.
+/// the first Command must be a join. every connection gets a Table of
+/// its own, with bots in the other seats. stacks reload when someone
+/// busts, and hands are dealt back to back until the client leaves.
+/// clients play on a Clock, so one that goes quiet can't stall its Table.
+pub struct Host {
+    bots: Vec<(String, Entrant)>,
+    clock: (Duration, Duration),
+}
+
+/// the baseline agents. the blueprint has to be entered
+/// explicitly, since it is loaded from disk.
+impl Default for Host {
+    fn default() -> Self {
+        let mut host = Self {
+            bots: Vec::new(),
+            clock: (Duration::from_secs(30), Duration::from_secs(120)),
+        };
+        host.enter("tag", || Box::new(Tag));
+        host.enter("threshold", || Box::new(Threshold::default()));
+        host.enter("raiser", || Box::new(Raiser));
+        host.enter("caller", || Box::new(Caller));
+        host.enter("random", || Box::new(Random));
+        host
+    }
+}
+
+impl Host {
+    /// offer a bot by name, or replace the one already offered. the
+    /// first bot is the one clients get when they don't ask for one.
+    pub fn enter<F>(&mut self, name: &str, bot: F)
+    where
+        F: Fn() -> Box<dyn Player> + Send + Sync + 'static,
+    {
+        self.bots.retain(|(n, _)| n != name);
+        self.bots.insert(0, (name.to_string(), Box::new(bot)));
+    }
+    /// how long clients get per decision, and in the bank for the session
+    pub fn set_clock(&mut self, limit: Duration, bank: Duration) {
+        self.clock = (limit, bank);
+    }
+    pub fn bots(&self) -> Vec<String> {
+        self.bots.iter().map(|(name, _)| name.clone()).collect()
+    }
+    pub fn router(self) -> Router {
+        Router::new()
+            .route("/bots", get(Self::list))
+            .route("/ws", get(Self::upgrade))
+            .with_state(Arc::new(self))
+    }
+    pub async fn serve(self, addr: impl ToSocketAddrs) -> std::io::Result<()> {
+        let listener = TcpListener::bind(addr).await?;
+        log::info!("hosting tables on {}", listener.local_addr()?);
+        axum::serve(listener, self.router()).await
+    }
+
+    async fn list(State(host): State<Arc<Self>>) -> Json<Vec<String>> {
+        Json(host.bots())
+    }
+    async fn upgrade(State(host): State<Arc<Self>>, ws: WebSocketUpgrade) -> Response {
+        ws.on_upgrade(move |socket| Self::connect(host, socket))
+    }
+
+    /// wait for a join, then relay between the socket and the Table
+    async fn connect(host: Arc<Self>, mut socket: WebSocket) {
+        let (seat, bot) = loop {
+            let error = match Self::recv(&mut socket).await {
+                None => return,
+                Some(Ok(Command::Join { seat, bot })) => match host.seat(seat, bot) {
+                    Ok(joined) => break joined,
+                    Err(message) => message,
+                },
+                Some(Ok(_)) => "join a table first".to_string(),
+                Some(Err(message)) => message,
+            };
+            if Self::send(&mut socket, &Update::Error { message: error })
+                .await
+                .is_err()
+            {
+                return;
+            }
+        };
+        let (updates, mut outbox) = tokio::sync::mpsc::unbounded_channel();
+        let (actions, inbox) = tokio::sync::mpsc::unbounded_channel();
+        let leaving = Arc::new(AtomicBool::new(false));
+        let table = {
+            let host = host.clone();
+            let leaving = leaving.clone();
+            tokio::task::spawn_blocking(move || host.host(seat, bot, updates, inbox, leaving))
+        };
+        loop {
+            tokio::select! {
+                update = outbox.recv() => match update {
+                    Some(update) => if Self::send(&mut socket, &update).await.is_err() { break },
+                    None => break,
+                },
+                command = Self::recv(&mut socket) => match command {
+                    None => break,
+                    Some(Ok(Command::Act { action })) => { let _ = actions.send(action); }
+                    Some(Ok(Command::Leave)) => leaving.store(true, Ordering::Relaxed),
+                    Some(Ok(Command::Join { .. })) => { let _ = Self::send(&mut socket, &Update::Error { message: "already seated".to_string() }).await; }
+                    Some(Err(message)) => { let _ = Self::send(&mut socket, &Update::Error { message }).await; }
+                },
+            }
+        }
+        drop(actions);
+        drop(outbox);
+        let _ = table.await;
+    }
+
+    /// which seat and bot a join asks for, if we have them
+    fn seat(&self, seat: Position, bot: Option<String>) -> Result<(Position, usize), String> {
+        if seat >= N {
+            return Err(format!("seat must be below {}", N));
+        }
+        match bot {
+            None => Ok((seat, 0)),
+            Some(name) => self
+                .bots
+                .iter()
+                .position(|(n, _)| *n == name)
+                .map(|bot| (seat, bot))
+                .ok_or(format!(
+                    "no bot named {}, try one of {}",
+                    name,
+                    self.bots().join(", ")
+                )),
+        }
+    }
+    /// play hands on a blocking thread until the client leaves or goes away
+    fn host(
+        &self,
+        seat: Position,
+        bot: usize,
+        updates: tokio::sync::mpsc::UnboundedSender<Update>,
+        inbox: tokio::sync::mpsc::UnboundedReceiver<Action>,
+        leaving: Arc<AtomicBool>,
+    ) {
+        let (name, entrant) = &self.bots[bot];
+        let (limit, bank) = self.clock;
+        let remote = Remote::from((seat, updates.clone(), inbox));
+        let mut remote = Some(Clock::from((remote, limit, bank)));
+        let players = (0..N)
+            .map(|s| match s == seat {
+                true => Box::new(remote.take().expect("one remote")) as Box<dyn Player>,
+                false => entrant(),
+            })
+            .collect::<Vec<Box<dyn Player>>>();
+        let mut table = Table::from(players);
+        table.set_reload(Some(crate::STACK));
+        let seated = Update::Seated {
+            seat,
+            bot: name.clone(),
+        };
+        if updates.send(seated).is_err() {
+            return;
+        }
+        while !leaving.load(Ordering::Relaxed) && !updates.is_closed() {
+            match table.play() {
+                Some(hand) => {
+                    let _ = updates.send(Update::from((seat, &hand)));
+                }
+                None => break,
+            }
+        }
+    }
+
+    async fn recv(socket: &mut WebSocket) -> Option<Result<Command, String>> {
+        loop {
+            match socket.recv().await? {
+                Ok(Message::Text(text)) => return Some(Command::try_from(text.as_str())),
+                Ok(Message::Close(_)) | Err(_) => return None,
+                Ok(_) => continue,
+            }
+        }
+    }
+    async fn send(socket: &mut WebSocket, update: &Update) -> Result<(), axum::Error> {
+        socket.send(Message::Text(String::from(update))).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::SinkExt;
+    use futures_util::StreamExt;
+    use tokio_tungstenite::tungstenite::Message as Frame;
+
+    #[tokio::test]
+    async fn play_against_a_bot() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, Host::default().router()).await });
+        let (mut ws, _) = tokio_tungstenite::connect_async(format!("ws://{}/ws", addr))
+            .await
+            .unwrap();
+        let recv = |text: Frame| serde_json::from_str::<Update>(text.to_text().unwrap()).unwrap();
+        let join = r#"{"type":"join","seat":1,"bot":"nobody"}"#;
+        ws.send(Frame::text(join)).await.unwrap();
+        assert!(matches!(
+            recv(ws.next().await.unwrap().unwrap()),
+            Update::Error { .. }
+        ));
+        let join = r#"{"type":"join","seat":1,"bot":"caller"}"#;
+        ws.send(Frame::text(join)).await.unwrap();
+        let mut results = 0;
+        while results < 3 {
+            match recv(ws.next().await.unwrap().unwrap()) {
+                Update::Seated { seat, bot } => assert!(seat == 1 && bot == "caller"),
+                Update::State(view) if !view.legal.is_empty() => {
+                    let action = view.legal.last().copied().unwrap();
+                    let act = Command::Act { action };
+                    let act = serde_json::to_string(&act).unwrap();
+                    ws.send(Frame::text(act)).await.unwrap();
+                }
+                Update::Result { shown, .. } => {
+                    assert!(shown[1].is_some());
+                    results += 1;
+                }
+                Update::Error { message } => panic!("{}", message),
+                _ => {}
+            }
+        }
+        ws.send(Frame::text(r#"{"type":"leave"}"#)).await.unwrap();
+    }
+
+    /// Tables run on blocking threads, which keeps tokio from moving
+    /// time on its own, so we keep moving it until the client times out
+    #[tokio::test(start_paused = true)]
+    async fn quiet_clients_time_out() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let mut host = Host::default();
+        host.set_clock(Duration::from_secs(1), Duration::ZERO);
+        tokio::spawn(async move { axum::serve(listener, host.router()).await });
+        let clock = tokio::spawn(async {
+            loop {
+                tokio::time::advance(Duration::from_secs(1)).await;
+            }
+        });
+        let (mut ws, _) = tokio_tungstenite::connect_async(format!("ws://{}/ws", addr))
+            .await
+            .unwrap();
+        ws.send(Frame::text(r#"{"type":"join","bot":"raiser"}"#))
+            .await
+            .unwrap();
+        let mut results = 0;
+        while results < 2 {
+            let frame = ws.next().await.unwrap().unwrap();
+            let update = serde_json::from_str::<Update>(frame.to_text().unwrap()).unwrap();
+            if let Update::Result {
+                results: outcomes, ..
+            } = update
+            {
+                assert!(outcomes[0].status == crate::gameplay::seat::State::Folding);
+                results += 1;
+            }
+        }
+        clock.abort();
+    }
+}
@@ -0,0 +1,3 @@
+pub mod host;
+pub mod protocol;
+pub mod remote;
@@ -0,0 +1,169 @@
+use crate::cards::board::Board;
+use crate::cards::hole::Hole;
+use crate::gameplay::action::Action;
+use crate::gameplay::game::Game;
+use crate::gameplay::game::Position;
+use crate::gameplay::ply::Ply;
+use crate::gameplay::seat::State;
+use crate::history::hand::HandHistory;
+use crate::history::hand::Outcome;
+use crate::history::hand::Play;
+use crate::Chips;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// what a client sends, one JSON object per message:
+///
+/// `{"type":"join","seat":0,"bot":"tag"}`
+/// `{"type":"act","action":"RAISE 6"}`
+/// `{"type":"leave"}`
+///
+/// actions are written just as in hand histories.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Command {
+    /// sit down at a new table, with a bot in every other seat
+    Join {
+        #[serde(default)]
+        seat: Position,
+        #[serde(default)]
+        bot: Option<String>,
+    },
+    /// our decision, when it's our turn
+    Act { action: Action },
+    /// stand up once the current hand is over
+    Leave,
+}
+
+/// what the server sends back, one JSON object per message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Update {
+    /// we're at a table, in this seat, against this bot
+    Seated { seat: Position, bot: String },
+    /// the table as we see it, at the start of a hand
+    /// and whenever it's our turn
+    State(View),
+    /// somebody acted, or a card fell
+    Play(Play),
+    /// how the hand ended. holes are shown for every
+    /// seat that went to showdown, and always our own
+    Result {
+        hand: usize,
+        board: Board,
+        results: Vec<Outcome>,
+        shown: Vec<Option<Hole>>,
+    },
+    /// something we sent didn't make sense
+    Error { message: String },
+}
+
+/// one seat's view of a live Game. legal is empty
+/// unless we're the seat that has to act.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct View {
+    pub seat: Position,
+    pub dealer: Position,
+    pub hole: Hole,
+    pub board: Board,
+    pub pot: Chips,
+    pub stacks: Vec<Chips>,
+    pub stakes: Vec<Chips>,
+    pub actor: Option<Position>,
+    pub legal: Vec<Action>,
+}
+
+impl From<(Position, &Game)> for View {
+    fn from((seat, game): (Position, &Game)) -> Self {
+        let actor = match game.player() {
+            Ply::Choice(actor) => Some(actor),
+            _ => None,
+        };
+        Self {
+            seat,
+            dealer: game.dealer(),
+            hole: game.seats()[seat].cards(),
+            board: game.board(),
+            pot: game.pot(),
+            stacks: game.seats().iter().map(|s| s.stack()).collect(),
+            stakes: game.seats().iter().map(|s| s.stake()).collect(),
+            actor,
+            legal: match actor {
+                Some(actor) if actor == seat => game.legal(),
+                _ => Vec::new(),
+            },
+        }
+    }
+}
+
+/// the end of a hand as this seat gets to see it
+impl From<(Position, &HandHistory)> for Update {
+    fn from((seat, hand): (Position, &HandHistory)) -> Self {
+        let shown = hand
+            .seats
+            .iter()
+            .zip(hand.results.iter())
+            .map(|(entry, outcome)| {
+                let showdown = hand.is_showdown() && outcome.status != State::Folding;
+                (entry.seat == seat || showdown).then_some(entry.hole)
+            })
+            .collect();
+        Update::Result {
+            hand: hand.id,
+            board: hand.board,
+            results: hand.results.clone(),
+            shown,
+        }
+    }
+}
+
+impl From<&Update> for String {
+    fn from(update: &Update) -> Self {
+        serde_json::to_string(update).expect("updates serialize")
+    }
+}
+
+impl TryFrom<&str> for Command {
+    type Error = String;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        serde_json::from_str(s).map_err(|e| format!("invalid command: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_commands() {
+        let join = Command::try_from(r#"{"type":"join","bot":"tag"}"#).unwrap();
+        let act = Command::try_from(r#"{"type":"act","action":"RAISE 6"}"#).unwrap();
+        let leave = Command::try_from(r#"{"type":"leave"}"#).unwrap();
+        assert!(
+            join == Command::Join {
+                seat: 0,
+                bot: Some("tag".to_string())
+            }
+        );
+        assert!(
+            act == Command::Act {
+                action: Action::Raise(6)
+            }
+        );
+        assert!(leave == Command::Leave);
+        assert!(Command::try_from(r#"{"type":"act","action":"BET"}"#).is_err());
+    }
+
+    #[test]
+    fn view_hides_other_holes() {
+        let game = Game::root();
+        let view = View::from((0, &game.seen_by(0)));
+        let json = String::from(&Update::State(view.clone()));
+        assert!(json.starts_with(r#"{"type":"state","#));
+        assert!(json.contains(&game.seats()[0].cards().to_string()));
+        assert!(!json.contains(&game.seats()[1].cards().to_string()));
+        assert!(view.actor == Some(0));
+        assert!(view.legal == game.legal());
+        assert!(View::from((1, &game.seen_by(1))).legal.is_empty());
+    }
+}
@@ -0,0 +1,97 @@
+use super::protocol::Update;
+use super::protocol::View;
+use crate::gameplay::action::Action;
+use crate::gameplay::game::Game;
+use crate::gameplay::game::Position;
+use crate::history::hand::Play;
+use crate::players::agent::Agent;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// a person on the other end of a connection.
+///
+/// the Table is synchronous, so it runs on a blocking thread while the
+/// connection lives on the async runtime, and channels bridge the two.
+/// Updates go out as the hand unfolds, and decide() waits until an
+/// Action comes back, for as long as its Clock allows. anything the
+/// Game refuses is answered with an error and we wait again. once the
+/// connection is gone, we check when it's free and fold otherwise.
+pub struct Remote {
+    seat: Position,
+    updates: UnboundedSender<Update>,
+    actions: UnboundedReceiver<Action>,
+}
+
+impl From<(Position, UnboundedSender<Update>, UnboundedReceiver<Action>)> for Remote {
+    fn from(
+        (seat, updates, actions): (Position, UnboundedSender<Update>, UnboundedReceiver<Action>),
+    ) -> Self {
+        Self {
+            seat,
+            updates,
+            actions,
+        }
+    }
+}
+
+impl Remote {
+    fn send(&self, update: Update) {
+        // a closed channel means the client is gone, which decide() handles
+        let _ = self.updates.send(update);
+    }
+}
+
+#[async_trait::async_trait]
+impl Agent for Remote {
+    async fn decide(&mut self, game: &Game) -> Action {
+        // anything sent after we last gave up waiting is stale by now
+        while self.actions.try_recv().is_ok() {}
+        self.send(Update::State(View::from((self.seat, game))));
+        loop {
+            match self.actions.recv().await {
+                None => return game.passive(),
+                Some(action) => match game.validate(&action) {
+                    Ok(()) => return action,
+                    Err(message) => self.send(Update::Error { message }),
+                },
+            }
+        }
+    }
+    fn begin(&mut self, game: &Game) {
+        // decide() asks for our Action, so the opening view doesn't
+        let mut view = View::from((self.seat, game));
+        view.legal.clear();
+        self.send(Update::State(view));
+    }
+    fn observe(&mut self, play: &Play) {
+        self.send(Update::Play(*play));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn refuses_illegal_actions() {
+        let (updates, mut sent) = tokio::sync::mpsc::unbounded_channel();
+        let (actions, received) = tokio::sync::mpsc::unbounded_channel();
+        let mut remote = Remote::from((0, updates, received));
+        let game = Game::root();
+        let decision = tokio::spawn(async move {
+            let action = remote.decide(&game).await;
+            (remote, action)
+        });
+        assert!(
+            matches!(sent.recv().await, Some(Update::State(view)) if view.legal == game.legal())
+        );
+        actions.send(Action::Check).unwrap();
+        assert!(matches!(sent.recv().await, Some(Update::Error { .. })));
+        actions.send(Action::Call(game.to_call())).unwrap();
+        let (mut remote, action) = decision.await.unwrap();
+        assert!(action == Action::Call(game.to_call()));
+        actions.send(Action::Check).unwrap();
+        drop(actions);
+        assert!(remote.decide(&game).await == Action::Fold);
+    }
+}
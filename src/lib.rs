@@ -1,7 +1,10 @@
+pub mod acpc;
 pub mod analysis;
 pub mod cards;
 pub mod clustering;
 pub mod gameplay;
+pub mod history;
+pub mod hosting;
 pub mod kmeans;
 pub mod mccfr;
 pub mod players;
@@ -9,7 +12,7 @@ pub mod search;
 pub mod transport;
 
 /// dimensional analysis types
-type Chips = i16;
+type Chips = i32; // ACPC stacks of 20_000 overflow i16 once pots are summed
 type Equity = f32;
 type Energy = f32;
 type Entropy = f32;
@@ -38,9 +41,16 @@ const KMEANS_FLOP_TRAINING_ITERATIONS: usize = 32;
 const CFR_BATCH_SIZE: usize = 256;
 const CFR_TREE_COUNT: usize = 1_048_576;
 const CFR_ITERATIONS: usize = CFR_TREE_COUNT / CFR_BATCH_SIZE;
+const CFR_CHECKPOINT: usize = 65_536 / CFR_BATCH_SIZE;
 const CFR_PRUNNING_PHASE: usize = 100_000_000 / CFR_BATCH_SIZE;
 const CFR_DISCOUNT_PHASE: usize = 100_000 / CFR_BATCH_SIZE;
 
+// best response parameters
+const BR_CHECKPOINT: usize = CFR_CHECKPOINT * 16;
+const BR_CHECKPOINT_DEALS: usize = 65_536;
+const LBR_HANDS: usize = 100_000;
+const LBR_ROLLOUTS: usize = 256;
+
 // regret matching parameters
 const REGRET_MIN: Utility = -3e5;
 const REGRET_MAX: Utility = Utility::MAX;
@@ -98,3 +108,37 @@ pub fn logs() {
     );
     simplelog::CombinedLogger::init(vec![term, file]).expect("initialize logger");
 }
+
+/// set by the first SIGINT or SIGTERM
+static INTERRUPTED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// has anyone asked us to stop? long jobs check between
+/// iterations, save what they have, and return early.
+pub fn interrupted() -> bool {
+    INTERRUPTED.load(std::sync::atomic::Ordering::SeqCst)
+}
+
+/// listen for SIGINT and SIGTERM on the current runtime.
+/// the first one asks long jobs to wrap up, and the second quits on the spot.
+pub fn interrupts() {
+    tokio::spawn(async {
+        #[cfg(unix)]
+        let mut terminate =
+            tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+                .expect("listen for SIGTERM");
+        loop {
+            #[cfg(unix)]
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {}
+                _ = terminate.recv() => {}
+            }
+            #[cfg(not(unix))]
+            tokio::signal::ctrl_c().await.expect("listen for SIGINT");
+            if INTERRUPTED.swap(true, std::sync::atomic::Ordering::SeqCst) {
+                log::warn!("interrupted again, quitting now");
+                std::process::exit(130);
+            }
+            log::warn!("interrupted, saving progress. interrupt again to quit now");
+        }
+    });
+}
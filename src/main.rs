@@ -47,16 +47,85 @@ use robopoker::*;
 //    2009. Computing Equilibria in Multiplayer Stochastic Games of Imperfect Information (http://www.cs.cmu.edu/~sandholm/stochgames.ijcai09.pdf). In IJCAI.
 //    2003. Using the Triangle Inequality to Accelerate-Means (https://cdn.aaai.org/ICML/2003/ICML03-022.pdf) In ICML.
 
+/// every stage runs in turn, picking up whatever is already on disk.
+/// the modes below skip ahead to use the blueprint instead of playing.
+#[derive(clap::Parser)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// regret minimizer to train with: vanilla, cfr+, pcfr+, linear, or dcfr[:α:β:γ]
+    #[arg(long, value_parser = minimizer)]
+    minimizer: Option<String>,
+    /// measure how far the blueprint is from equilibrium
+    #[arg(long)]
+    exploitability: bool,
+    /// measure what a local best response wins off the blueprint
+    #[arg(long)]
+    lbr: bool,
+    /// host tables over HTTP and WebSocket
+    #[arg(long)]
+    serve: bool,
+}
+
+/// reject unknown regret minimizers before we get anywhere near training
+fn minimizer(name: &str) -> Result<String, String> {
+    Box::<dyn crate::mccfr::minimizer::Minimizer>::try_from(name).map(|_| name.to_string())
+}
+
 #[tokio::main]
 async fn main() {
+    // Bad flags are a usage error, before anything else happens.
+    let args = <Args as clap::Parser>::parse();
     // Boring stuff
     crate::logs();
+    // Ctrl-C saves progress before quitting, twice quits right away.
+    crate::interrupts();
+    // Put the blueprint we already have to use, without training any further.
+    if (args.exploitability || args.lbr || args.serve) && !crate::mccfr::blueprint::Solver::done() {
+        log::error!("no blueprint on disk yet, run without flags to train one");
+        return;
+    }
+    // Measure how far the blueprint is from equilibrium, if asked to.
+    if args.exploitability {
+        crate::mccfr::blueprint::Solver::evaluate();
+        return;
+    }
+    // Measure what a greedy exploiter wins off the blueprint in the real game.
+    if args.lbr {
+        crate::players::lbr::Lbr::evaluate();
+        return;
+    }
+    // Host tables over HTTP and WebSocket, if asked to, instead of playing here.
+    if args.serve {
+        let mut host = crate::hosting::host::Host::default();
+        host.enter("robot", || Box::new(crate::players::robot::Robot::load()));
+        host.serve("0.0.0.0:8888").await.expect("serve tables");
+        return;
+    }
     // The k-means earth mover's distance hand-clustering algorithm.
     crate::clustering::encoding::Encoder::learn();
+    if crate::interrupted() {
+        return;
+    }
     // Monet Carlo counter-factual regret minimization. External sampling, alternating regret updates, linear weighting schedules.
-    crate::mccfr::blueprint::Solver::train();
+    // Pick the update rule with --minimizer=vanilla|cfr+|pcfr+|linear|dcfr[:α:β:γ], discounted by default.
+    // Resuming keeps whichever one the checkpoint was trained with.
+    let minimizer = args
+        .minimizer
+        .map(|name| Box::try_from(name.as_str()).expect("validated by clap"));
+    if let Err(e) = crate::mccfr::blueprint::Solver::train(minimizer) {
+        log::error!("{}", e);
+        return;
+    }
+    if crate::interrupted() {
+        return;
+    }
     // Let's see what we've learned.
     crate::analysis::cli::CLI::new().await.run().await;
-    // After 100s of CPU-days of training in the arena, the CPU is ready to see you.
-    crate::gameplay::game::Game::play();
+    // After 100s of CPU-days of training in the arena, the blueprint is ready to see you.
+    crate::gameplay::table::Table::from(vec![
+        Box::new(crate::players::human::Human::default())
+            as Box<dyn crate::players::player::Player>,
+        Box::new(crate::players::robot::Robot::load()) as Box<dyn crate::players::player::Player>,
+    ])
+    .run();
 }
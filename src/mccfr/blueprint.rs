@@ -1,14 +1,21 @@
 use super::counterfactual::Counterfactual;
 use super::info::Info;
+use super::minimizer::Minimizer;
 use super::node::Node;
 use super::partition::Partition;
 use super::player::Player;
 use super::policy::Policy;
 use super::profile::Profile;
+use super::response::BestResponse;
+use super::sampled::SampledResponse;
 use super::sampler::Sampler;
 use super::spot::Spot;
 use super::tree::Branch;
 use super::tree::Tree;
+use crate::cards::hole::Hole;
+use crate::gameplay::game::Game;
+use crate::gameplay::ply::Ply;
+use crate::Utility;
 use rayon::iter::IntoParallelIterator;
 use rayon::iter::ParallelIterator;
 
@@ -30,19 +37,81 @@ pub struct Solver {
     sampler: Sampler,
 }
 
+impl From<(Profile, Sampler)> for Solver {
+    fn from((profile, sampler): (Profile, Sampler)) -> Self {
+        Self { profile, sampler }
+    }
+}
+
 impl Solver {
     /// after training, use the learned Profile to advise
-    /// a Spot on how to play.
-    #[allow(unused)]
-    fn advise(&self, spot: Spot) -> Policy {
-        let bucket = self.sampler.bucket(&spot);
-        let policy = self.profile.policy(&bucket);
+    /// a Spot on how to play. None if the Spot falls outside
+    /// the abstract tree, or into a Bucket we never visited.
+    pub fn advise(&self, spot: &Spot) -> Option<Policy> {
+        let bucket = self.sampler.bucket(spot)?;
+        let policy = self.profile.lookup(&bucket)?;
         let policy = spot.coalesce(policy);
-        Policy::from(policy)
+        Some(policy).filter(|p| !p.inner().is_empty())
+    }
+
+    /// how much a best response wins against us in the abstract
+    /// game, in milli-big-blinds per hand. zero at equilibrium.
+    /// each seat's response is exact, and walks every board.
+    pub fn exploitability(&self) -> Utility {
+        let blinds = (crate::S_BLIND, crate::B_BLIND);
+        let root = Game::seated([(crate::STACK, Hole::empty()); crate::N], 0, blinds);
+        let chips = [Ply::Choice(0), Ply::Choice(1)]
+            .into_par_iter()
+            .map(|ply| BestResponse::from((&self.profile, &self.sampler, Player(ply))))
+            .map(|response| response.value(&root))
+            .sum::<Utility>()
+            / crate::N as Utility;
+        chips / crate::B_BLIND as Utility * 1000.
+    }
+    /// a quicker estimate of exploitability, in milli-big-blinds
+    /// per hand, with its standard error. each seat learns a
+    /// SampledResponse over this many deals, then plays it over
+    /// as many fresh ones. the response is learned from samples,
+    /// so this is a lower bound, up to that error.
+    pub fn sampled_exploitability(&self, deals: usize) -> (Utility, Utility) {
+        let (mean, variance) = [Ply::Choice(0), Ply::Choice(1)]
+            .into_iter()
+            .map(|ply| SampledResponse::from((&self.profile, &self.sampler, Player(ply))))
+            .map(|mut response| {
+                response.learn(deals);
+                response.value(deals)
+            })
+            .map(|tally| (tally.mean(), tally.error().powi(2)))
+            .fold((0., 0.), |(m, v), (mean, variance)| {
+                (m + mean, v + variance)
+            });
+        let seats = crate::N as f64;
+        let mbb = 1000. / crate::B_BLIND as f64;
+        let mean = mean / seats * mbb;
+        let error = variance.sqrt() / seats * mbb;
+        (mean as Utility, error as Utility)
+    }
+    /// measure the saved blueprint against its best response
+    pub fn evaluate() {
+        let solver = Self::load();
+        log::info!(
+            "measuring exploitability of {} buckets over every board",
+            solver.profile.buckets()
+        );
+        let mbb = solver.exploitability();
+        if crate::interrupted() {
+            log::warn!("exploitability measurement interrupted");
+            return;
+        }
+        log::info!("exploitability {:.2} mbb/hand", mbb);
     }
 
+    /// check (by filename) if a finished profile and encoder have been saved to disk
+    pub fn done() -> bool {
+        Profile::done() && crate::clustering::encoding::Encoder::done()
+    }
     /// load existing profile and encoder from disk
-    fn load() -> Self {
+    pub fn load() -> Self {
         Self {
             profile: Profile::load(),
             sampler: Sampler::load(),
@@ -52,23 +121,44 @@ impl Solver {
     /// here's the training loop. infosets might be generated
     /// in parallel later. infosets come pre-filtered
     /// for the traverser. regret and policy updates are
-    /// encapsulated by Profile, but we are yet to impose
-    /// a learning schedule for regret or policy.
-    pub fn train() {
-        if Self::done() {
+    /// encapsulated by Profile, and the learning schedule
+    /// for them is up to the Minimizer we train with.
+    ///
+    /// training picks up from the furthest Profile on disk,
+    /// so a crash only costs the epochs since the last checkpoint.
+    /// it keeps the Minimizer that Profile was trained with, and
+    /// refuses to switch to a different one halfway through.
+    pub fn train(minimizer: Option<Box<dyn Minimizer>>) -> Result<(), String> {
+        let mut profile = Profile::resume();
+        if profile.epochs() >= crate::CFR_ITERATIONS {
             log::info!("skipping regret minimization");
-        } else {
-            log::info!("starting regret minimization");
-            Self::load().solve();
+            return Ok(());
         }
+        if let Some(minimizer) = minimizer {
+            let trained = profile.minimizer().to_string();
+            if profile.epochs() > 0 && minimizer.to_string() != trained {
+                return Err(format!(
+                    "profile was trained with {} for {} epochs, not {}",
+                    trained,
+                    profile.epochs(),
+                    minimizer
+                ));
+            }
+            profile.set_minimizer(minimizer);
+        }
+        log::info!(
+            "starting {} regret minimization at epoch {}",
+            profile.minimizer(),
+            profile.epochs()
+        );
+        Self::from((profile, Sampler::load())).solve();
+        Ok(())
     }
-    /// check (by filename) if a blueprint solver has been saved to disk.
-    fn done() -> bool {
-        Profile::done()
-    }
-    /// the main training loop.
+    /// the main training loop. checkpoints every so often,
+    /// and once more if we're interrupted, after the current batch.
     fn solve(&mut self) {
-        while self.profile.next() <= crate::CFR_ITERATIONS {
+        while self.profile.epochs() < crate::CFR_ITERATIONS {
+            self.profile.next();
             for counterfactual in self.updates() {
                 let ref regret = counterfactual.regret();
                 let ref policy = counterfactual.policy();
@@ -76,10 +166,43 @@ impl Solver {
                 self.profile.add_regret(bucket, regret);
                 self.profile.add_policy(bucket, policy);
             }
+            if crate::interrupted() {
+                log::warn!(
+                    "stopping regret minimization at epoch {}",
+                    self.profile.epochs()
+                );
+                return self.profile.checkpoint();
+            }
+            if self.profile.epochs() % crate::CFR_CHECKPOINT == 0 {
+                self.profile.checkpoint();
+            }
+            if self.profile.epochs() % crate::BR_CHECKPOINT == 0 {
+                self.report();
+            }
         }
         self.profile.save("blueprint");
     }
 
+    /// how our Minimizer is converging, by the same
+    /// exploitability measure we hold the blueprint to.
+    /// the exact one takes too long to run mid-training, so we
+    /// sample enough deals that the standard error we log is small
+    /// next to how much exploitability falls between reports.
+    /// an interrupted measurement isn't worth reporting.
+    fn report(&self) {
+        let (mbb, error) = self.sampled_exploitability(crate::BR_CHECKPOINT_DEALS);
+        if crate::interrupted() {
+            return;
+        }
+        log::info!(
+            "{} exploitability at least {:.0} ± {:.0} mbb/hand at epoch {}",
+            self.profile.minimizer(),
+            mbb,
+            error,
+            self.profile.epochs()
+        );
+    }
+
     /// compute regret and policy updates for a batch of Trees.
     fn updates(&mut self) -> Vec<Counterfactual> {
         self.batch()
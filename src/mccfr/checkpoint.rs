@@ -0,0 +1,63 @@
+use super::profile::Profile;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// everything about a saved Profile besides its weights.
+///
+/// the discount schedule, the training phase, and which player walks
+/// the Tree all follow from the epoch count, so a Profile can't pick
+/// training back up without it. it lives next to the weights, as JSON.
+/// we also note which Minimizer the weights were trained with.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub epochs: usize,
+    pub batch: usize,
+    pub buckets: usize,
+    pub time: u64,
+    #[serde(default)]
+    pub minimizer: String,
+}
+
+impl From<&Profile> for Checkpoint {
+    fn from(profile: &Profile) -> Self {
+        Self {
+            epochs: profile.epochs(),
+            batch: crate::CFR_BATCH_SIZE,
+            buckets: profile.buckets(),
+            time: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .expect("time moves slow")
+                .as_secs(),
+            minimizer: profile.minimizer().to_string(),
+        }
+    }
+}
+
+impl Checkpoint {
+    fn file(name: &str) -> String {
+        format!("{}.profile.json", name)
+    }
+    /// the metadata saved alongside this Profile, if there is any
+    pub fn load(name: &str) -> Option<Self> {
+        let json = std::fs::read_to_string(Self::file(name)).ok()?;
+        serde_json::from_str(&json).ok()
+    }
+    /// written to the side and renamed into place,
+    /// so a crash never leaves half a file behind
+    pub fn save(&self, name: &str) {
+        let file = Self::file(name);
+        let json = serde_json::to_string(self).expect("checkpoints serialize");
+        std::fs::write(format!("{}.tmp", file), json).expect("write checkpoint");
+        std::fs::rename(format!("{}.tmp", file), file).expect("rename checkpoint");
+    }
+}
+
+impl std::fmt::Display for Checkpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "epoch {} ({} trees per epoch, {} buckets, {})",
+            self.epochs, self.batch, self.buckets, self.minimizer
+        )
+    }
+}
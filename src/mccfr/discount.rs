@@ -1,6 +1,17 @@
+use super::memory::Memory;
+use super::minimizer::Minimizer;
+use super::phase::Phase;
+use crate::Probability;
 use crate::Utility;
 
-#[derive(Debug)]
+/// Discounted CFR. before each epoch adds its regret, accumulated
+/// regret is discounted by t^α / (t^α + 1) when this epoch's regret
+/// is positive, by t^ω / (t^ω + 1) when it's negative, and the average
+/// policy by (t / (t + 1))^γ. regret is only discounted during
+/// Phase::Discount; after that it accumulates undiscounted. each
+/// epoch's regret is floored at REGRET_MIN before it's added, so
+/// one disastrous sample can't bury an Edge for good.
+#[derive(Debug, Clone, Copy)]
 pub struct Discount {
     period: usize, // interval between strategy updates.
     alpha: f32,    // α parameter. controls recency bias.
@@ -8,16 +19,30 @@ pub struct Discount {
     gamma: f32,    // γ parameter. controls recency bias.
 }
 
-impl Discount {
-    pub const fn default() -> &'static Self {
-        &Self {
+impl Default for Discount {
+    fn default() -> Self {
+        Self {
             period: 1,
             alpha: 1.5,
             omega: 0.5,
             gamma: 2.0,
         }
     }
+}
+
+/// (α, β, γ) in the paper's notation, where our ω is their β
+impl From<(f32, f32, f32)> for Discount {
+    fn from((alpha, omega, gamma): (f32, f32, f32)) -> Self {
+        Self {
+            period: 1,
+            alpha,
+            omega,
+            gamma,
+        }
+    }
+}
 
+impl Discount {
     pub fn policy(&self, t: usize) -> f32 {
         (t as f32 / (t as f32 + 1.)).powf(self.gamma)
     }
@@ -36,3 +61,69 @@ impl Discount {
         }
     }
 }
+
+impl Minimizer for Discount {
+    fn add_regret(&self, t: usize, memory: &mut Memory, regret: Utility) {
+        let discount = match Phase::from(t) {
+            Phase::Discount => self.regret(t, regret),
+            Phase::Explore => 1.,
+            Phase::Prune => 1.,
+        };
+        let regret = regret.clamp(crate::REGRET_MIN, crate::REGRET_MAX);
+        memory.add_regret(discount, regret);
+    }
+    fn add_policy(&self, t: usize, memory: &mut Memory, policy: Probability) {
+        memory.add_policy(self.policy(t), policy);
+    }
+}
+
+impl std::fmt::Display for Discount {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "dcfr:{}:{}:{}", self.alpha, self.omega, self.gamma)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// the default schedule, as Profile ran it before Minimizers:
+    /// discounted by the sign of this epoch's regret, and only
+    /// until we leave Phase::Discount, with that regret floored.
+    #[test]
+    fn default_schedule() {
+        let discount = Discount::default();
+        let minimizer = Box::<dyn Minimizer>::default();
+        for t in [
+            1,
+            2,
+            64,
+            crate::CFR_DISCOUNT_PHASE,
+            crate::CFR_PRUNNING_PHASE,
+        ] {
+            for (accumulated, regret) in [
+                (10., 3.),
+                (10., -3.),
+                (-10., 3.),
+                (-10., -3.),
+                (10., crate::REGRET_MIN * 2.),
+            ] {
+                let mut expected = Memory::from((accumulated, 0.));
+                let mut observed = Memory::from((accumulated, 0.));
+                let factor = match Phase::from(t) {
+                    Phase::Discount => discount.regret(t, regret),
+                    _ => 1.,
+                };
+                expected.add_regret(factor, regret.max(crate::REGRET_MIN));
+                minimizer.add_regret(t, &mut observed, regret);
+                assert!(observed == expected);
+                let mut expected = Memory::from((0., accumulated));
+                let mut observed = Memory::from((0., accumulated));
+                expected.add_policy(discount.policy(t), 1.);
+                minimizer.add_policy(t, &mut observed, 1.);
+                assert!(observed == expected);
+            }
+        }
+        assert!(Phase::from(crate::CFR_DISCOUNT_PHASE) == Phase::Explore);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::gameplay::action::Action;
+use crate::gameplay::game::Game;
 use crate::mccfr::odds::Odds;
-use crate::{Arbitrary, Chips};
+use crate::{Arbitrary, Chips, Utility};
 use std::hash::Hash;
 
 #[derive(Debug, Clone, Copy, Hash, Ord, PartialOrd, PartialEq, Eq)]
@@ -50,6 +51,31 @@ impl From<Odds> for Edge {
     }
 }
 
+/// the concrete Action an Edge stands for in a given Game.
+/// raises are sized against the pot and snapped into the legal range,
+/// so this is the same sizing whether the Game is abstract or live.
+impl From<(&Game, Edge)> for Action {
+    fn from((game, edge): (&Game, Edge)) -> Self {
+        match edge {
+            Edge::Check => Action::Check,
+            Edge::Fold => Action::Fold,
+            Edge::Draw => Action::Draw(game.draw()),
+            Edge::Call => Action::Call(game.to_call()),
+            Edge::Shove => Action::Shove(game.to_shove()),
+            Edge::Raise(o) => {
+                let min = game.to_raise();
+                let max = game.to_shove();
+                let bet = (game.pot() as Utility * Utility::from(o)) as Chips;
+                match bet {
+                    bet if bet >= max => Action::Shove(max),
+                    bet if bet <= min => Action::Raise(min),
+                    _ => Action::Raise(bet),
+                }
+            }
+        }
+    }
+}
+
 /// usize bijection
 impl From<Edge> for u8 {
     fn from(edge: Edge) -> Self {
@@ -0,0 +1,32 @@
+use super::memory::Memory;
+use super::minimizer::Minimizer;
+use crate::Probability;
+use crate::Utility;
+
+/// Linear CFR. epoch t weighs t in both regret and average policy,
+/// which we get by discounting everything so far by t / (t + 1).
+/// the same as DCFR with α = β = γ = 1.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Linear;
+
+impl Linear {
+    fn discount(t: usize) -> f32 {
+        t as f32 / (t as f32 + 1.)
+    }
+}
+
+impl Minimizer for Linear {
+    fn add_regret(&self, t: usize, memory: &mut Memory, regret: Utility) {
+        let regret = memory.regret() * Self::discount(t) + regret;
+        memory.set_regret(regret.clamp(crate::REGRET_MIN, crate::REGRET_MAX));
+    }
+    fn add_policy(&self, t: usize, memory: &mut Memory, policy: Probability) {
+        memory.add_policy(Self::discount(t), policy);
+    }
+}
+
+impl std::fmt::Display for Linear {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "linear")
+    }
+}
@@ -6,6 +6,8 @@ use crate::Utility;
 pub struct Memory {
     regret: Utility,
     policy: Probability,
+    /// the regret we expect next epoch, for predictive Minimizers
+    prediction: Utility,
 }
 
 impl Memory {
@@ -15,12 +17,18 @@ impl Memory {
     pub fn policy(&self) -> Probability {
         self.policy
     }
+    pub fn prediction(&self) -> Utility {
+        self.prediction
+    }
     pub fn set_regret(&mut self, value: Utility) {
         self.regret = value;
     }
     pub fn set_policy(&mut self, value: Probability) {
         self.policy = value;
     }
+    pub fn set_prediction(&mut self, value: Utility) {
+        self.prediction = value;
+    }
     pub fn add_regret(&mut self, discount: f32, value: Utility) {
         self.regret *= discount;
         self.regret += value;
@@ -33,7 +41,11 @@ impl Memory {
 
 impl From<(f32, f32)> for Memory {
     fn from((regret, policy): (f32, f32)) -> Self {
-        Self { regret, policy }
+        Self {
+            regret,
+            policy,
+            prediction: 0.,
+        }
     }
 }
 
@@ -44,6 +56,7 @@ impl Arbitrary for Memory {
         Self {
             regret: rng.gen(),
             policy: rng.gen(),
+            prediction: rng.gen(),
         }
     }
 }
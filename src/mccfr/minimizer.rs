@@ -0,0 +1,134 @@
+use super::discount::Discount;
+use super::linear::Linear;
+use super::memory::Memory;
+use super::plus::Plus;
+use super::predictive::Predictive;
+use super::vanilla::Vanilla;
+use crate::Probability;
+use crate::Utility;
+
+/// a regret minimization subroutine.
+///
+/// Profile computes instantaneous regret and current policy at every
+/// Bucket it trains, and a Minimizer decides what to make of them:
+/// how regret accumulates in each Edge's Memory, how the current
+/// policy folds into the average one, and what regret the next
+/// current policy is matched against. t is the training epoch.
+///
+/// which one we train with is picked at training time, so different
+/// rules can be compared on the same abstraction.
+pub trait Minimizer: Send + Sync + std::fmt::Display {
+    /// fold this epoch's instantaneous regret into an Edge's Memory
+    fn add_regret(&self, t: usize, memory: &mut Memory, regret: Utility);
+    /// fold this epoch's current policy into an Edge's average policy
+    fn add_policy(&self, t: usize, memory: &mut Memory, policy: Probability);
+    /// the regret an Edge's current policy is matched against,
+    /// which is just what we've accumulated unless we're predicting
+    fn matched(&self, memory: &Memory) -> Utility {
+        memory.regret()
+    }
+}
+
+impl Default for Box<dyn Minimizer> {
+    fn default() -> Self {
+        Box::new(Discount::default())
+    }
+}
+
+/// pick a Minimizer by name: vanilla, cfr+, pcfr+, linear,
+/// or dcfr, which also takes its parameters as dcfr:α:β:γ
+impl TryFrom<&str> for Box<dyn Minimizer> {
+    type Error = String;
+    fn try_from(name: &str) -> Result<Self, Self::Error> {
+        let mut parts = name.split(':');
+        match parts.next() {
+            Some("vanilla") => Ok(Box::new(Vanilla)),
+            Some("cfr+") => Ok(Box::new(Plus)),
+            Some("pcfr+") => Ok(Box::new(Predictive)),
+            Some("linear") => Ok(Box::new(Linear)),
+            Some("dcfr") => match parts.collect::<Vec<_>>().as_slice() {
+                [] => Ok(Box::new(Discount::default())),
+                [alpha, beta, gamma] => {
+                    let parse = |x: &str| x.parse::<f32>().map_err(|e| e.to_string());
+                    let params = (parse(alpha)?, parse(beta)?, parse(gamma)?);
+                    Ok(Box::new(Discount::from(params)))
+                }
+                _ => Err(format!("dcfr takes α:β:γ, not {}", name)),
+            },
+            _ => Err(format!("unknown regret minimizer {}", name)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// self-play of rock paper scissors, where rock wins double.
+    /// equilibrium plays (1/4, 1/2, 1/4), which an average policy
+    /// of any of these should be close to after a while.
+    fn converges(minimizer: &dyn Minimizer) -> bool {
+        const PAYOFF: [[Utility; 3]; 3] = [[0., -1., 2.], [1., 0., -1.], [-2., 1., 0.]];
+        let mut memories = vec![Memory::default(); 3];
+        for t in 1..=10_000 {
+            let current = memories
+                .iter()
+                .map(|m| minimizer.matched(m).max(crate::POLICY_MIN))
+                .collect::<Vec<_>>();
+            let sum = current.iter().sum::<Utility>();
+            let current = current.iter().map(|r| r / sum).collect::<Vec<_>>();
+            let values = PAYOFF.map(|row| row.iter().zip(&current).map(|(u, p)| u * p).sum());
+            let expected = values
+                .iter()
+                .zip(&current)
+                .map(|(v, p)| v * p)
+                .sum::<Utility>();
+            for (i, memory) in memories.iter_mut().enumerate() {
+                minimizer.add_regret(t, memory, values[i] - expected);
+                minimizer.add_policy(t, memory, current[i]);
+            }
+        }
+        let sum = memories.iter().map(Memory::policy).sum::<Probability>();
+        memories
+            .iter()
+            .map(|m| m.policy() / sum)
+            .zip([0.25, 0.5, 0.25])
+            .all(|(p, q)| (p - q).abs() < 0.02)
+    }
+
+    #[test]
+    fn every_minimizer_converges() {
+        for name in ["vanilla", "cfr+", "pcfr+", "linear", "dcfr", "dcfr:1.5:0:2"] {
+            let minimizer = Box::<dyn Minimizer>::try_from(name).unwrap();
+            assert!(converges(minimizer.as_ref()), "{} diverged", minimizer);
+        }
+    }
+
+    /// Profile hands over instantaneous regret as is,
+    /// and each Minimizer floors what it accumulates
+    #[test]
+    fn minimizers_floor_their_own_regret() {
+        let regret = crate::REGRET_MIN * 10.;
+        for (name, floor) in [
+            ("vanilla", crate::REGRET_MIN),
+            ("cfr+", 0.),
+            ("pcfr+", 0.),
+            ("linear", crate::REGRET_MIN),
+            ("dcfr", crate::REGRET_MIN),
+        ] {
+            let minimizer = Box::<dyn Minimizer>::try_from(name).unwrap();
+            let mut memory = Memory::default();
+            minimizer.add_regret(1, &mut memory, regret);
+            assert!(memory.regret() == floor, "{} floored wrong", minimizer);
+        }
+        let mut memory = Memory::default();
+        Predictive.add_regret(1, &mut memory, regret);
+        assert!(memory.prediction() == regret);
+    }
+
+    #[test]
+    fn unknown_minimizers() {
+        assert!(Box::<dyn Minimizer>::try_from("dcfr:1.5").is_err());
+        assert!(Box::<dyn Minimizer>::try_from("hedge").is_err());
+    }
+}
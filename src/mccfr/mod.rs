@@ -1,21 +1,29 @@
 pub mod blueprint;
 pub mod bucket;
+pub mod checkpoint;
 pub mod counterfactual;
 pub mod data;
 pub mod discount;
 pub mod edge;
 pub mod info;
+pub mod linear;
 pub mod memory;
+pub mod minimizer;
 pub mod node;
 pub mod odds;
 pub mod partition;
 pub mod path;
 pub mod phase;
 pub mod player;
+pub mod plus;
 pub mod policy;
+pub mod predictive;
 pub mod profile;
 pub mod regret;
+pub mod response;
+pub mod sampled;
 pub mod sampler;
 pub mod spot;
 pub mod strategy;
 pub mod tree;
+pub mod vanilla;
@@ -7,7 +7,6 @@ use crate::gameplay::action::Action;
 use crate::gameplay::ply::Ply;
 use crate::mccfr::data::Data;
 use crate::mccfr::edge::Edge;
-use crate::Chips;
 use crate::Utility;
 use petgraph::graph::DiGraph;
 use petgraph::graph::NodeIndex;
@@ -152,24 +151,7 @@ impl<'tree> Node<'tree> {
     /// within range of legal bet sizes, so sometimes Raise(5:1) yields
     /// an identical Game node as Raise(1:1) or Shove.
     pub fn actionization(&self, edge: &Edge) -> Action {
-        let game = self.data().game();
-        match &edge {
-            Edge::Check => Action::Check,
-            Edge::Fold => Action::Fold,
-            Edge::Draw => Action::Draw(game.draw()),
-            Edge::Call => Action::Call(game.to_call()),
-            Edge::Shove => Action::Shove(game.to_shove()),
-            Edge::Raise(o) => {
-                let min = game.to_raise();
-                let max = game.to_shove();
-                let bet = (game.pot() as Utility * Utility::from(*o)) as Chips;
-                match bet {
-                    bet if bet >= max => Action::Shove(max),
-                    bet if bet <= min => Action::Raise(min),
-                    _ => Action::Raise(bet),
-                }
-            }
-        }
+        Action::from((self.data().game(), *edge))
     }
     /// generalization of mapping a concrete Action into a set of abstract Vec<Edge>
     /// this is mostly useful for enumerating a set of desired Raises
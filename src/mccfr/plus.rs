@@ -0,0 +1,26 @@
+use super::memory::Memory;
+use super::minimizer::Minimizer;
+use crate::Probability;
+use crate::Utility;
+
+/// regret matching+, as in CFR+. accumulated regret is floored
+/// at zero, so an Edge is back in play as soon as it looks good
+/// again, and later epochs weigh linearly more in the average policy.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Plus;
+
+impl Minimizer for Plus {
+    fn add_regret(&self, _: usize, memory: &mut Memory, regret: Utility) {
+        let regret = memory.regret() + regret;
+        memory.set_regret(regret.clamp(0., crate::REGRET_MAX));
+    }
+    fn add_policy(&self, t: usize, memory: &mut Memory, policy: Probability) {
+        memory.add_policy(1., t as Probability * policy);
+    }
+}
+
+impl std::fmt::Display for Plus {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "cfr+")
+    }
+}
@@ -0,0 +1,34 @@
+use super::memory::Memory;
+use super::minimizer::Minimizer;
+use crate::Probability;
+use crate::Utility;
+
+/// Predictive CFR+. regret accumulates as in CFR+, floored at zero,
+/// but the current policy is matched against accumulated regret plus
+/// a prediction of the next epoch's regret, which is just the last
+/// one we saw. when consecutive epochs agree, as they do more and more
+/// near equilibrium, we get a step ahead. epoch t weighs t² in the
+/// average policy, which we get by discounting it by (t / (t + 1))².
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Predictive;
+
+impl Minimizer for Predictive {
+    fn add_regret(&self, _: usize, memory: &mut Memory, regret: Utility) {
+        let accumulated = memory.regret() + regret;
+        memory.set_regret(accumulated.clamp(0., crate::REGRET_MAX));
+        memory.set_prediction(regret);
+    }
+    fn add_policy(&self, t: usize, memory: &mut Memory, policy: Probability) {
+        let discount = (t as f32 / (t as f32 + 1.)).powi(2);
+        memory.add_policy(discount, policy);
+    }
+    fn matched(&self, memory: &Memory) -> Utility {
+        (memory.regret() + memory.prediction()).max(0.)
+    }
+}
+
+impl std::fmt::Display for Predictive {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "pcfr+")
+    }
+}
@@ -1,6 +1,7 @@
+use super::checkpoint::Checkpoint;
 use super::counterfactual::Counterfactual;
-use super::discount::Discount;
 use super::memory::Memory;
+use super::minimizer::Minimizer;
 use super::phase::Phase;
 use super::policy::Policy;
 use super::regret::Regret;
@@ -29,18 +30,19 @@ use std::usize;
 /// we keep a (Regret, AveragePolicy, CurrentPolicy)
 /// for each distinct Bucket(Path, Abstraction) that we visit.
 /// we also count how many training epochs we've run so far.
-/// i feel like this can be broken up into
-/// - Minimizer: handles policy and regret updates by implementing some regret-minimzation subroutine
-/// - Profile: stores policy & regret values. used by reference for a lot of calculations,
-/// such as Reach, Utility, MinimizerRegretVector, MinimizerPolicyVector, SampleTree, etc.
+/// how regret and policy updates accumulate is up to our Minimizer,
+/// while we store the values and use them for Reach, Utility,
+/// regret and policy vectors, Tree sampling, etc.
 #[derive(Default)]
 pub struct Profile {
     iterations: usize,
     strategies: BTreeMap<Bucket, Strategy>,
+    minimizer: Box<dyn Minimizer>,
 }
 
 impl Profile {
     const PREFIX: &'static str = "blueprint";
+    const CHECKPOINT: &'static str = "checkpoint";
     /// check (by filename) if a profile has been saved to disk.
     pub fn done() -> bool {
         std::fs::metadata(format!("{}.profile.pgcopy", Self::PREFIX)).is_ok()
@@ -53,6 +55,27 @@ impl Profile {
             Self::default()
         }
     }
+    /// the furthest trained Profile on disk, whether a finished
+    /// blueprint or a checkpoint, to pick training back up from
+    pub fn resume() -> Self {
+        [Self::PREFIX, Self::CHECKPOINT]
+            .into_iter()
+            .filter(|name| std::fs::metadata(format!("{}.profile.pgcopy", name)).is_ok())
+            .max_by_key(|name| Checkpoint::load(name).map_or(0, |c| c.epochs))
+            .map(Self::from)
+            .unwrap_or_default()
+    }
+    /// save where resume() will find it, mid-training
+    pub fn checkpoint(&self) {
+        self.save(Self::CHECKPOINT);
+    }
+    /// the regret minimization subroutine we train with
+    pub fn minimizer(&self) -> &dyn Minimizer {
+        self.minimizer.as_ref()
+    }
+    pub fn set_minimizer(&mut self, minimizer: Box<dyn Minimizer>) {
+        self.minimizer = minimizer;
+    }
     /// increment Epoch counter
     /// and return current count
     pub fn next(&mut self) -> usize {
@@ -110,8 +133,6 @@ impl Profile {
             .outgoing()
             .into_iter()
             .map(|a| (a.clone(), self.immediate_regret(infoset, a)))
-            .map(|(a, r)| (a, r.max(crate::REGRET_MIN)))
-            .map(|(a, r)| (a, r.min(crate::REGRET_MAX)))
             .inspect(|(a, r)| log::trace!("{:16} ! {:>10 }", format!("{:?}", a), r))
             .inspect(|(_, r)| assert!(!r.is_nan()))
             .inspect(|(_, r)| assert!(!r.is_infinite()))
@@ -147,20 +168,13 @@ impl Profile {
     pub fn add_regret(&mut self, bucket: &Bucket, regrets: &Regret) {
         log::trace!("update regret @ {}", bucket);
         let t = self.epochs();
-        let phase = self.phase();
-        let discount = Discount::default();
         let strategy = self
             .strategies
             .get_mut(bucket)
             .expect("bucket been witnessed");
         for (action, &regret) in regrets.inner() {
             let decision = strategy.get_mut(action).expect("action been witnessed");
-            let discount = match phase {
-                Phase::Discount => discount.regret(t, regret),
-                Phase::Explore => 1.,
-                Phase::Prune => 1.,
-            };
-            decision.add_regret(discount, regret);
+            self.minimizer.add_regret(t, decision, regret);
             log::trace!("{} : {}", action, decision.regret());
         }
     }
@@ -168,15 +182,13 @@ impl Profile {
     pub fn add_policy(&mut self, bucket: &Bucket, policys: &Policy) {
         log::trace!("update policy @ {}", bucket);
         let t = self.epochs();
-        let discount = Discount::default();
         let strategy = self
             .strategies
             .get_mut(bucket)
             .expect("bucket been witnessed");
         for (action, &policy) in policys.inner() {
-            let discount = discount.policy(t);
             let decision = strategy.get_mut(action).expect("action been witnessed");
-            decision.add_policy(discount, policy);
+            self.minimizer.add_policy(t, decision, policy);
             log::trace!("{} : {}", action, decision.policy());
         }
     }
@@ -206,6 +218,10 @@ impl Profile {
             _ => Player(Ply::Choice(1)),
         }
     }
+    /// how many Buckets we've visited
+    pub fn buckets(&self) -> usize {
+        self.strategies.len()
+    }
     /// full set of available actions and their weights (not Probabilities)
     pub fn policy(&self, bucket: &Bucket) -> Policy {
         self.strategies
@@ -213,6 +229,10 @@ impl Profile {
             .expect("bucket must exist")
             .policy()
     }
+    /// the Policy at a Bucket, if training ever visited it
+    pub fn lookup(&self, bucket: &Bucket) -> Option<Policy> {
+        self.strategies.get(bucket).map(Strategy::policy)
+    }
     /// absolute Probability. only used for Tree sampling in Monte Carlo Trainer.
     pub fn weight(&self, bucket: &Bucket, edge: &Edge) -> Probability {
         self.strategies
@@ -277,17 +297,18 @@ impl Profile {
     /// upon visiting any Node inthis Infoset,
     /// how much cumulative Utility have we missed out on
     /// for not having followed this Edge?
+    /// our Minimizer may have a prediction to add.
     fn cumulated_regret(&self, infoset: &Info, edge: &Edge) -> Utility {
         assert!(infoset.node().player() == self.walker());
         let node = infoset.node();
         let bucket = node.bucket();
-        self.strategies
+        let memory = self
+            .strategies
             .get(bucket)
             .expect("bucket has been witnessed")
             .get(edge)
-            .expect("action has been witnessed")
-            .regret()
-            / self.epochs() as Utility
+            .expect("action has been witnessed");
+        self.minimizer.matched(memory) / self.epochs() as Utility
     }
     /// conditional on being in this Infoset,
     /// distributed across all its head Nodes,
@@ -453,8 +474,10 @@ impl From<&str> for Profile {
         let mut buffer = [0u8; 2];
         reader.seek(SeekFrom::Start(19)).expect("seek past header");
         while reader.read_exact(&mut buffer).is_ok() {
-            if u16::from_be_bytes(buffer) == 6 {
-                // we expect 6 fields per record
+            let fields = u16::from_be_bytes(buffer);
+            if fields == 6 || fields == 7 {
+                // we expect 7 fields per record,
+                // or 6 if saved before we kept predictions
                 // 4 indexed fields
                 reader.read_u32::<BE>().expect("past path length");
                 let past = Path::from(reader.read_u64::<BE>().expect("read past path"));
@@ -464,11 +487,18 @@ impl From<&str> for Profile {
                 let future = Path::from(reader.read_u64::<BE>().expect("read future path"));
                 reader.read_u32::<BE>().expect("edge length");
                 let edge = Edge::from(reader.read_u64::<BE>().expect("read edge"));
-                // 2 unindexed fields
+                // 2 or 3 unindexed fields
                 reader.read_u32::<BE>().expect("regret length");
                 let regret = reader.read_f32::<BE>().expect("read regret");
                 reader.read_u32::<BE>().expect("policy length");
                 let policy = reader.read_f32::<BE>().expect("read policy");
+                let prediction = match fields {
+                    7 => {
+                        reader.read_u32::<BE>().expect("prediction length");
+                        reader.read_f32::<BE>().expect("read prediction")
+                    }
+                    _ => 0.,
+                };
                 // idempotent insert
                 let bucket = Bucket::from((past, abs, future));
                 let memory = strategies
@@ -478,33 +508,62 @@ impl From<&str> for Profile {
                     .or_insert_with(Memory::default);
                 memory.set_regret(regret);
                 memory.set_policy(policy);
+                memory.set_prediction(prediction);
                 continue;
             } else {
                 break;
             }
         }
+        let (iterations, minimizer) = match Checkpoint::load(name) {
+            Some(checkpoint) => {
+                if checkpoint.batch != crate::CFR_BATCH_SIZE {
+                    log::warn!("profile was trained {} trees per epoch", checkpoint.batch);
+                }
+                (checkpoint.epochs, Self::trained(&checkpoint))
+            }
+            None => {
+                log::warn!("no training metadata for {}, starting epochs over", name);
+                (0, Box::default())
+            }
+        };
         Self {
-            iterations: 0,
+            iterations,
             strategies,
+            minimizer,
         }
     }
 }
 
 impl Profile {
-    /// persist the Profile to disk
+    /// the Minimizer a Checkpoint was trained with. checkpoints
+    /// from before we kept track were trained with the default.
+    fn trained(checkpoint: &Checkpoint) -> Box<dyn Minimizer> {
+        match checkpoint.minimizer.as_str() {
+            "" => Box::default(),
+            name => Box::try_from(name).unwrap_or_else(|e| {
+                log::warn!("{}, resuming with the default", e);
+                Box::default()
+            }),
+        }
+    }
+    /// persist the Profile to disk, along with the Checkpoint that lets
+    /// training resume from it. weights are written to the side and
+    /// renamed into place, so a crash never leaves half a file behind.
     pub fn save(&self, name: &str) {
-        log::info!("saving blueprint");
+        log::info!("saving {} at epoch {}", name, self.epochs());
         use byteorder::WriteBytesExt;
         use byteorder::BE;
         use std::fs::File;
         use std::io::Write;
-        let ref mut file = File::create(format!("{name}.profile.pgcopy")).expect("touch");
+        let path = format!("{name}.profile.pgcopy");
+        let temp = format!("{path}.tmp");
+        let file = &mut File::create(&temp).expect("touch");
         file.write_all(b"PGCOPY\n\xFF\r\n\0").expect("header");
         file.write_u32::<BE>(0).expect("flags");
         file.write_u32::<BE>(0).expect("extension");
         for (bucket, strategy) in self.strategies.iter() {
             for (edge, memory) in strategy.iter() {
-                const N_FIELDS: u16 = 6;
+                const N_FIELDS: u16 = 7;
                 file.write_u16::<BE>(N_FIELDS).unwrap();
                 // 4 indexed fields
                 file.write_u32::<BE>(size_of::<u64>() as u32).unwrap();
@@ -515,14 +574,19 @@ impl Profile {
                 file.write_u64::<BE>(u64::from(bucket.2)).unwrap();
                 file.write_u32::<BE>(size_of::<u64>() as u32).unwrap();
                 file.write_u64::<BE>(u64::from(edge.clone())).unwrap();
-                // 2 unindexed fields
+                // 3 unindexed fields
                 file.write_u32::<BE>(size_of::<f32>() as u32).unwrap();
                 file.write_f32::<BE>(memory.regret()).unwrap();
                 file.write_u32::<BE>(size_of::<f32>() as u32).unwrap();
                 file.write_f32::<BE>(memory.policy()).unwrap();
+                file.write_u32::<BE>(size_of::<f32>() as u32).unwrap();
+                file.write_f32::<BE>(memory.prediction()).unwrap();
             }
         }
         file.write_u16::<BE>(0xFFFF).expect("trailer");
+        file.sync_all().expect("flush profile");
+        std::fs::rename(temp, path).expect("rename profile");
+        Checkpoint::from(self).save(name);
     }
 }
 
@@ -533,6 +597,7 @@ impl Arbitrary for Profile {
             strategies: (0..100)
                 .map(|_| (Bucket::random(), Strategy::random()))
                 .collect(),
+            minimizer: Box::default(),
         }
     }
 }
@@ -573,13 +638,29 @@ mod tests {
     fn persistence() {
         let name = "test";
         let file = format!("{}.profile.pgcopy", name);
-        let save = Profile::random();
+        let mut save = Profile::random();
+        save.iterations = 7;
         save.save(name);
         let load = Profile::from(name);
         std::fs::remove_file(file).unwrap();
+        std::fs::remove_file(format!("{}.profile.json", name)).unwrap();
+        assert!(load.epochs() == 7);
+        assert!(load.buckets() == save.buckets());
         assert!(std::iter::empty()
             .chain(save.strategies.iter().zip(load.strategies.iter()))
             .chain(load.strategies.iter().zip(save.strategies.iter()))
             .all(|((s1, l1), (s2, l2))| s1 == s2 && l1 == l2));
     }
+
+    #[test]
+    fn remembers_minimizer() {
+        let name = "remembers";
+        let mut save = Profile::random();
+        save.set_minimizer(Box::try_from("cfr+").unwrap());
+        save.save(name);
+        let load = Profile::from(name);
+        std::fs::remove_file(format!("{}.profile.pgcopy", name)).unwrap();
+        std::fs::remove_file(format!("{}.profile.json", name)).unwrap();
+        assert!(load.minimizer().to_string() == "cfr+");
+    }
 }
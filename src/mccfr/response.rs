@@ -0,0 +1,533 @@
+use super::bucket::Bucket;
+use super::data::Data;
+use super::edge::Edge;
+use super::player::Player;
+use super::profile::Profile;
+use super::sampler::Sampler;
+use super::tree::Branch;
+use super::tree::Tree;
+use crate::cards::card::Card;
+use crate::cards::hand::Hand;
+use crate::cards::hands::HandIterator;
+use crate::cards::hole::Hole;
+use crate::cards::strength::Strength;
+use crate::clustering::abstraction::Abstraction;
+use crate::gameplay::action::Action;
+use crate::gameplay::game::Game;
+use crate::gameplay::ply::Ply;
+use crate::gameplay::seat::State;
+use crate::Probability;
+use crate::Utility;
+use petgraph::graph::NodeIndex;
+use rayon::iter::IntoParallelIterator;
+use rayon::iter::ParallelIterator;
+use std::collections::BTreeMap;
+
+/// the exact best response to a fixed Profile in the abstract game.
+///
+/// we walk the public tree: every abstract Edge, and every card
+/// chance could deal. the opponent's range over all of its holes
+/// rides along, each weighted by how often the Profile would have
+/// brought it here. at each of our decisions, every hole we might
+/// hold takes whichever Edge is worth most to it against that range.
+/// our cards are never abstracted, so this is the real best response
+/// to the Profile within its betting abstraction.
+///
+/// values are counterfactual: summed over the opponent's holes,
+/// weighted by its reach and by chance, for each of our holes.
+/// boards are dealt out in parallel, one rayon task per public
+/// chance outcome. from the root of a hand that's every runout,
+/// so this is a long offline job. later streets are quick.
+pub struct BestResponse<'a> {
+    profile: &'a Profile,
+    sampler: &'a Sampler,
+    walker: Player,
+    holes: Vec<Hole>,
+}
+
+/// what every hole looks like once this much of the board is out:
+/// its Abstraction, and on the river, how it ranks at showdown
+struct Deal {
+    board: Hand,
+    abstractions: Vec<Abstraction>,
+    ranking: Vec<(Strength, usize)>,
+}
+
+impl<'a> From<(&'a Profile, &'a Sampler, Player)> for BestResponse<'a> {
+    fn from((profile, sampler, walker): (&'a Profile, &'a Sampler, Player)) -> Self {
+        Self {
+            profile,
+            sampler,
+            walker,
+            holes: HandIterator::from((2, Hand::empty()))
+                .map(Hole::from)
+                .collect(),
+        }
+    }
+}
+
+impl<'a> BestResponse<'a> {
+    /// what our response wins per hand from this public Game, in chips,
+    /// averaged over every way both holes could have been dealt.
+    /// zero if we're interrupted, which callers should check for.
+    pub fn value(&self, root: &Game) -> Utility {
+        let board = Hand::from(root.board());
+        let deal = self.deal(board);
+        let reach = self
+            .holes
+            .iter()
+            .map(|hole| if Self::blocks(*hole, board) { 0. } else { 1. })
+            .collect::<Vec<Probability>>();
+        let values = self.walk(root, &[], &deal, &reach);
+        let opponents = Self::choose(board.complement().size() - 2, 2) as Utility;
+        let (total, ours) = values
+            .iter()
+            .zip(reach.iter())
+            .filter(|(_, live)| **live > 0.)
+            .fold((0., 0.), |(t, n), (v, _)| (t + v, n + 1.));
+        total / ours / opponents
+    }
+
+    /// each of our holes' counterfactual value at the end of this path
+    fn walk(
+        &self,
+        root: &Game,
+        path: &[(Edge, Game)],
+        deal: &Deal,
+        reach: &[Probability],
+    ) -> Vec<Utility> {
+        if crate::interrupted() || reach.iter().all(|p| *p == 0.) {
+            return vec![0.; self.holes.len()];
+        }
+        let game = path.last().map(|(_, game)| game).unwrap_or(root);
+        match game.player() {
+            Ply::Terminal => self.payoff(game, deal, reach),
+            Ply::Chance => self.chance(root, path, deal, reach),
+            Ply::Choice(_) => {
+                let tree = Self::spine(root, path);
+                let node = tree.at(NodeIndex::new(path.len()));
+                let edges = node.continuations();
+                let child = |edge: &Edge| {
+                    let mut path = path.to_vec();
+                    path.push((*edge, game.apply(node.actionization(edge))));
+                    path
+                };
+                if node.player() == self.walker {
+                    edges
+                        .iter()
+                        .map(|edge| self.walk(root, &child(edge), deal, reach))
+                        .reduce(|a, b| a.iter().zip(b).map(|(a, b)| a.max(b)).collect())
+                        .expect("decisions have edges")
+                } else {
+                    let policies = self.policies(node.bucket(), &edges, deal, reach);
+                    edges
+                        .iter()
+                        .enumerate()
+                        .map(|(i, edge)| {
+                            let reach = reach
+                                .iter()
+                                .zip(deal.abstractions.iter())
+                                .map(|(p, abs)| match policies.get(abs) {
+                                    Some(policy) => p * policy[i],
+                                    None => 0.,
+                                })
+                                .collect::<Vec<Probability>>();
+                            self.walk(root, &child(edge), deal, &reach)
+                        })
+                        .reduce(|a, b| a.iter().zip(b).map(|(a, b)| a + b).collect())
+                        .expect("decisions have edges")
+                }
+            }
+        }
+    }
+    /// deal every next card or three, each board in its own task
+    fn chance(
+        &self,
+        root: &Game,
+        path: &[(Edge, Game)],
+        deal: &Deal,
+        reach: &[Probability],
+    ) -> Vec<Utility> {
+        let game = path.last().map(|(_, game)| game).unwrap_or(root);
+        let n = game.board().street().n_revealed();
+        let unseen = deal.board.complement().size() - 2 * crate::N;
+        let weight = 1. / Self::choose(unseen, n) as Utility;
+        HandIterator::from((n, deal.board))
+            .collect::<Vec<Hand>>()
+            .into_par_iter()
+            .map(|cards| {
+                let next = self.deal(Hand::add(deal.board, cards));
+                let reach = self.blocked(reach, cards);
+                let mut path = path.to_vec();
+                path.push((Edge::Draw, game.apply(Action::Draw(cards))));
+                let values = self.walk(root, &path, &next, &reach);
+                self.blocked(&values, cards)
+            })
+            .reduce(
+                || vec![0.; self.holes.len()],
+                |a, b| a.iter().zip(b).map(|(a, b)| a + b).collect(),
+            )
+            .into_iter()
+            .map(|v| v * weight)
+            .collect()
+    }
+    /// how the opponent splits its reach over these Edges, for each
+    /// Abstraction it might be holding here. uniform where untrained.
+    fn policies(
+        &self,
+        bucket: &Bucket,
+        edges: &[Edge],
+        deal: &Deal,
+        reach: &[Probability],
+    ) -> BTreeMap<Abstraction, Vec<Probability>> {
+        let mut policies = BTreeMap::new();
+        for (_, abs) in reach
+            .iter()
+            .zip(deal.abstractions.iter())
+            .filter(|(p, _)| **p > 0.)
+        {
+            policies.entry(*abs).or_insert_with(|| {
+                let bucket = Bucket::from((bucket.0, *abs, bucket.2));
+                let weights = match self.profile.lookup(&bucket) {
+                    None => vec![1.; edges.len()],
+                    Some(policy) => edges
+                        .iter()
+                        .map(|edge| policy.inner().get(edge).copied().unwrap_or(0.))
+                        .collect(),
+                };
+                let total = weights.iter().sum::<Probability>();
+                if total > 0. {
+                    weights.iter().map(|w| w / total).collect()
+                } else {
+                    vec![1. / edges.len() as Probability; edges.len()]
+                }
+            });
+        }
+        policies
+    }
+    /// what each of our holes wins at a terminal Game, against the range.
+    /// the abstract game is unraked, so a fold costs the folder what it
+    /// put in, and a showdown swaps whatever both of us matched.
+    fn payoff(&self, game: &Game, deal: &Deal, reach: &[Probability]) -> Vec<Utility> {
+        let Player(Ply::Choice(me)) = self.walker else {
+            unreachable!("walkers make choices")
+        };
+        let us = &game.seats()[me];
+        let them = &game.seats()[(me + 1) % crate::N];
+        match (us.state(), them.state()) {
+            (State::Folding, _) => self.folds(reach, -(us.spent() as Utility)),
+            (_, State::Folding) => self.folds(reach, them.spent() as Utility),
+            _ => self.showdown(deal, reach, us.spent().min(them.spent()) as Utility),
+        }
+    }
+    /// chips won by each hole against every opponent hole it doesn't block
+    fn folds(&self, reach: &[Probability], chips: Utility) -> Vec<Utility> {
+        let total = reach.iter().sum::<Probability>();
+        let cards = self.by_card(reach);
+        self.holes
+            .iter()
+            .zip(reach.iter())
+            .map(|(hole, p)| {
+                let [a, b] = Self::cards(*hole);
+                chips * (total - cards[a] - cards[b] + p)
+            })
+            .collect()
+    }
+    /// chips won by each hole against the range at showdown. sweeping
+    /// holes in order of strength, each one beats the reach of all
+    /// weaker holes and loses that of all stronger ones, less whatever
+    /// shares a card with it.
+    fn showdown(&self, deal: &Deal, reach: &[Probability], chips: Utility) -> Vec<Utility> {
+        let mut values = vec![0.; self.holes.len()];
+        let groups = deal
+            .ranking
+            .chunk_by(|a, b| a.0 == b.0)
+            .collect::<Vec<&[(Strength, usize)]>>();
+        for (sign, groups) in [
+            (1., groups.iter().collect::<Vec<_>>()),
+            (-1., groups.iter().rev().collect::<Vec<_>>()),
+        ] {
+            let mut total = 0.;
+            let mut cards = [0.; 52];
+            for group in groups {
+                for (_, i) in group.iter() {
+                    let [a, b] = Self::cards(self.holes[*i]);
+                    values[*i] += sign * chips * (total - cards[a] - cards[b]);
+                }
+                for (_, i) in group.iter() {
+                    let [a, b] = Self::cards(self.holes[*i]);
+                    total += reach[*i];
+                    cards[a] += reach[*i];
+                    cards[b] += reach[*i];
+                }
+            }
+        }
+        values
+    }
+
+    /// abstract and, on the river, rank every hole the board leaves live
+    fn deal(&self, board: Hand) -> Deal {
+        let live = self
+            .holes
+            .iter()
+            .enumerate()
+            .filter(|(_, hole)| !Self::blocks(**hole, board));
+        let abstractions = self
+            .holes
+            .iter()
+            .map(|hole| match Self::blocks(*hole, board) {
+                true => Abstraction::from(0u64),
+                false => self.sampler.perceived(*hole, board),
+            })
+            .collect();
+        let mut ranking = match board.size() {
+            5 => live
+                .map(|(i, hole)| (Strength::from(Hand::add(Hand::from(*hole), board)), i))
+                .collect(),
+            _ => Vec::new(),
+        };
+        ranking.sort();
+        Deal {
+            board,
+            abstractions,
+            ranking,
+        }
+    }
+    /// the same per-hole vector, zeroed wherever a hole holds one of these cards
+    fn blocked(&self, values: &[Utility], cards: Hand) -> Vec<Utility> {
+        self.holes
+            .iter()
+            .zip(values.iter())
+            .map(|(hole, v)| if Self::blocks(*hole, cards) { 0. } else { *v })
+            .collect()
+    }
+    /// total reach of the holes holding each card
+    fn by_card(&self, reach: &[Probability]) -> [Probability; 52] {
+        let mut cards = [0.; 52];
+        for (hole, p) in self.holes.iter().zip(reach.iter()) {
+            let [a, b] = Self::cards(*hole);
+            cards[a] += p;
+            cards[b] += p;
+        }
+        cards
+    }
+    /// the public Game states so far as a Tree of one branch,
+    /// so its last Node can tell us its Edges and Bucket
+    fn spine(root: &Game, path: &[(Edge, Game)]) -> Tree {
+        let data = |game: &Game| Data::from((*game, Abstraction::from(0u64)));
+        let mut tree = Tree::empty(Player(root.player()));
+        let mut head = tree.insert(data(root)).index();
+        for (edge, game) in path {
+            head = tree.attach(Branch(data(game), *edge, head)).index();
+        }
+        tree
+    }
+    fn cards(hole: Hole) -> [usize; 2] {
+        let cards = Vec::<Card>::from(Hand::from(hole));
+        [u8::from(cards[0]) as usize, u8::from(cards[1]) as usize]
+    }
+    fn blocks(hole: Hole, cards: Hand) -> bool {
+        u64::from(Hand::from(hole)) & u64::from(cards) != 0
+    }
+    fn choose(n: usize, k: usize) -> usize {
+        (0..k).fold(1, |x, i| x * (n - i) / (i + 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::isomorphism::Isomorphism;
+    use crate::cards::observation::Observation;
+    use crate::cards::street::Street;
+    use crate::clustering::encoding::Encoder;
+    use crate::mccfr::node::Node;
+
+    /// a hand checked down to the turn's end on a 4 chip pot,
+    /// with 4 chips behind each, waiting on the river
+    fn turned() -> Game {
+        let blinds = (crate::S_BLIND, crate::B_BLIND);
+        let mut game = Game::seated([(6, Hole::empty()); crate::N], 0, blinds);
+        game = game.apply(Action::Call(1));
+        game = game.apply(Action::Check);
+        for cards in ["2c 7d Ts", "Jh"] {
+            game = game.apply(Action::Draw(Hand::try_from(cards).unwrap()));
+            game = game.apply(Action::Check);
+            game = game.apply(Action::Check);
+        }
+        game
+    }
+    fn river() -> Game {
+        turned().apply(Action::Draw(Hand::try_from("Kd").unwrap()))
+    }
+
+    /// every hole on these boards in the same Abstraction
+    fn sampler(boards: impl Iterator<Item = Hand>) -> Sampler {
+        let mut encoder = Encoder::default();
+        for board in boards {
+            for hole in HandIterator::from((2, board)) {
+                let observation = Observation::from((hole, board));
+                encoder.assign(&Abstraction::from(0.5), &Isomorphism::from(observation));
+            }
+        }
+        Sampler::from(encoder)
+    }
+
+    /// what one of our holes is worth at a Node, one opponent hole
+    /// at a time, with the opponent playing uniformly
+    fn naive(node: &Node, me: usize, ours: Hole, theirs: &[(Hole, Probability)]) -> Utility {
+        let game = node.data().game();
+        let children = node.children();
+        match game.player() {
+            Ply::Terminal => theirs
+                .iter()
+                .map(|(hole, p)| {
+                    let game = game.holding(me, ours).holding(1 - me, *hole);
+                    p * game.settlements()[me].pnl() as Utility
+                })
+                .sum(),
+            Ply::Choice(seat) if seat == me => children
+                .iter()
+                .map(|child| naive(child, me, ours, theirs))
+                .fold(Utility::MIN, Utility::max),
+            _ => {
+                let n = children.len() as Probability;
+                let theirs = theirs.iter().map(|(h, p)| (*h, p / n)).collect::<Vec<_>>();
+                children
+                    .iter()
+                    .map(|child| naive(child, me, ours, &theirs))
+                    .sum()
+            }
+        }
+    }
+
+    /// the whole betting tree from a Game, with no cards left to deal
+    fn expand(root: Game) -> Tree {
+        let data = |game: Game| Data::from((game, Abstraction::from(0u64)));
+        let mut tree = Tree::empty(Player(root.player()));
+        let mut todo = vec![tree.insert(data(root)).index()];
+        while let Some(head) = todo.pop() {
+            let node = tree.at(head);
+            let game = *node.data().game();
+            let branches = match game.player() {
+                Ply::Terminal => vec![],
+                _ => node
+                    .continuations()
+                    .into_iter()
+                    .map(|edge| Branch(data(game.apply(node.actionization(&edge))), edge, head))
+                    .collect::<Vec<Branch>>(),
+            };
+            for branch in branches {
+                todo.push(tree.attach(branch).index());
+            }
+        }
+        tree
+    }
+
+    #[test]
+    fn showdowns_match_pairs() {
+        let board = Hand::try_from("2c 7d Ts Jh Kd").unwrap();
+        let profile = Profile::default();
+        let sampler = sampler(std::iter::once(board));
+        let response = BestResponse::from((&profile, &sampler, Player(Ply::Choice(0))));
+        let deal = response.deal(board);
+        let reach = response
+            .holes
+            .iter()
+            .enumerate()
+            .map(|(i, hole)| match BestResponse::blocks(*hole, board) {
+                true => 0.,
+                false => (i % 7) as Probability,
+            })
+            .collect::<Vec<Probability>>();
+        let showdown = response.showdown(&deal, &reach, 3.);
+        let folds = response.folds(&reach, 2.);
+        let strength = |hole: Hole| Strength::from(Hand::add(Hand::from(hole), board));
+        for (i, ours) in response.holes.iter().enumerate().step_by(37) {
+            if BestResponse::blocks(*ours, board) {
+                continue;
+            }
+            let (mut won, mut live) = (0., 0.);
+            for (theirs, p) in response.holes.iter().zip(reach.iter()) {
+                if BestResponse::blocks(*theirs, Hand::add(board, Hand::from(*ours))) {
+                    continue;
+                }
+                live += p;
+                won += p * match strength(*ours).cmp(&strength(*theirs)) {
+                    std::cmp::Ordering::Greater => 3.,
+                    std::cmp::Ordering::Equal => 0.,
+                    std::cmp::Ordering::Less => -3.,
+                };
+            }
+            assert!((showdown[i] - won).abs() < 1e-2);
+            assert!((folds[i] - 2. * live).abs() < 1e-2);
+        }
+    }
+
+    #[test]
+    fn river_matches_naive() {
+        let game = river();
+        let board = Hand::from(game.board());
+        let profile = Profile::default();
+        let sampler = sampler(std::iter::once(board));
+        let tree = expand(game);
+        let root = tree.at(NodeIndex::new(0));
+        for me in 0..crate::N {
+            let response = BestResponse::from((&profile, &sampler, Player(Ply::Choice(me))));
+            let deal = response.deal(board);
+            let reach = response
+                .holes
+                .iter()
+                .map(|hole| {
+                    if BestResponse::blocks(*hole, board) {
+                        0.
+                    } else {
+                        1.
+                    }
+                })
+                .collect::<Vec<Probability>>();
+            let values = response.walk(&game, &[], &deal, &reach);
+            for (i, ours) in response.holes.iter().enumerate().step_by(97) {
+                if BestResponse::blocks(*ours, board) {
+                    continue;
+                }
+                let dead = Hand::add(board, Hand::from(*ours));
+                let theirs = HandIterator::from((2, dead))
+                    .map(|hand| (Hole::from(hand), 1.))
+                    .collect::<Vec<(Hole, Probability)>>();
+                let expected = naive(&root, me, *ours, &theirs);
+                assert!((values[i] - expected).abs() < 1e-2 * expected.abs().max(1.));
+            }
+        }
+    }
+
+    #[test]
+    fn exploits_uniform_play() {
+        let game = river();
+        assert!(game.board().street() == Street::Rive);
+        let profile = Profile::default();
+        let sampler = sampler(std::iter::once(Hand::from(game.board())));
+        let value = (0..crate::N)
+            .map(|me| BestResponse::from((&profile, &sampler, Player(Ply::Choice(me)))))
+            .map(|response| response.value(&game))
+            .sum::<Utility>();
+        assert!(value > 0.);
+    }
+
+    #[test]
+    fn chance_averages_runouts() {
+        let game = turned();
+        let turn = Hand::from(game.board());
+        let rivers = HandIterator::from((1, turn)).collect::<Vec<Hand>>();
+        let profile = Profile::default();
+        let boards = rivers.iter().map(|river| Hand::add(turn, *river));
+        let sampler = sampler(boards.chain(std::iter::once(turn)));
+        let response = BestResponse::from((&profile, &sampler, Player(Ply::Choice(1))));
+        let runouts = rivers
+            .iter()
+            .map(|river| response.value(&game.apply(Action::Draw(*river))))
+            .sum::<Utility>()
+            / rivers.len() as Utility;
+        assert!((response.value(&game) - runouts).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,226 @@
+use super::bucket::Bucket;
+use super::edge::Edge;
+use super::node::Node;
+use super::player::Player;
+use super::profile::Profile;
+use super::sampler::Sampler;
+use super::tree::Branch;
+use super::tree::Tree;
+use crate::gameplay::arena::Tally;
+use crate::Probability;
+use crate::Utility;
+use petgraph::graph::NodeIndex;
+use rand::distributions::WeightedIndex;
+use rand::prelude::Distribution;
+use rayon::iter::IntoParallelIterator;
+use rayon::iter::ParallelIterator;
+use std::collections::BTreeMap;
+
+/// one walker decision seen on one deal:
+/// what following an Edge out of a Bucket was worth
+type Sample = (Bucket, Edge, Utility);
+
+/// a response to a fixed Profile learned from sampled deals,
+/// for when the exact BestResponse is too slow to wait for.
+///
+/// every deal draws its own cards, the opponent samples one Edge
+/// from its Profile policy, and we, the walker, expand every Edge.
+/// deals are independent, so each batch of them runs in parallel.
+///
+/// summing what every Edge was worth over many deals estimates its
+/// value at that Bucket, opponent reach included, since the opponent
+/// and chance took those Edges as often as they would. we respond
+/// with whichever Edge is worth most in each Bucket, and keep refining
+/// as deals come in. Buckets the opponent never trained are played
+/// uniformly, and so are ours until we've seen them.
+///
+/// a Bucket we rarely reach is decided on a handful of samples, and
+/// whatever we get wrong there only costs us, so what this wins
+/// is a lower bound on exploitability, up to sampling noise.
+pub struct SampledResponse<'a> {
+    profile: &'a Profile,
+    sampler: &'a Sampler,
+    walker: Player,
+    values: BTreeMap<Bucket, BTreeMap<Edge, Utility>>,
+    choices: BTreeMap<Bucket, Edge>,
+}
+
+impl<'a> From<(&'a Profile, &'a Sampler, Player)> for SampledResponse<'a> {
+    fn from((profile, sampler, walker): (&'a Profile, &'a Sampler, Player)) -> Self {
+        Self {
+            profile,
+            sampler,
+            walker,
+            values: BTreeMap::default(),
+            choices: BTreeMap::default(),
+        }
+    }
+}
+
+impl<'a> SampledResponse<'a> {
+    /// the Edge we respond with at a Bucket, once we've seen it
+    pub fn choice(&self, bucket: &Bucket) -> Option<&Edge> {
+        self.choices.get(bucket)
+    }
+    /// refine our response over this many deals.
+    /// stops early, after the current batch, if we're interrupted.
+    pub fn learn(&mut self, deals: usize) {
+        for _ in 0..deals.div_ceil(crate::CFR_BATCH_SIZE) {
+            let samples = self.batch().into_iter().flat_map(|(_, s)| s);
+            self.record(samples.collect());
+            if crate::interrupted() {
+                break;
+            }
+        }
+    }
+    /// what our response wins per hand, in chips, tallied over
+    /// this many fresh deals so that its mean comes with a standard
+    /// error. the deals we learned from would flatter us, so we
+    /// don't reuse them. stops early, after the current batch,
+    /// if we're interrupted.
+    pub fn value(&self, deals: usize) -> Tally {
+        let mut tally = Tally::default();
+        for _ in 0..deals.div_ceil(crate::CFR_BATCH_SIZE) {
+            for (value, _) in self.batch() {
+                tally += Tally::from(value as f64);
+            }
+            if crate::interrupted() {
+                break;
+            }
+        }
+        tally
+    }
+
+    /// accumulate what each Edge was worth, and
+    /// respond with the best of them where it changed
+    fn record(&mut self, samples: Vec<Sample>) {
+        for (bucket, edge, value) in samples {
+            let values = self.values.entry(bucket).or_default();
+            *values.entry(edge).or_default() += value;
+            let best = values
+                .iter()
+                .max_by(|a, b| a.1.total_cmp(b.1))
+                .map(|(edge, _)| *edge)
+                .expect("just inserted");
+            self.choices.insert(bucket, best);
+        }
+    }
+    /// sample and evaluate a batch of deals in parallel
+    fn batch(&self) -> Vec<(Utility, Vec<Sample>)> {
+        (0..crate::CFR_BATCH_SIZE)
+            .into_par_iter()
+            .map(|_| self.sample())
+            .map(|tree| {
+                let mut samples = Vec::new();
+                let value = self.evaluate(&tree.at(NodeIndex::new(0)), &mut samples);
+                (value, samples)
+            })
+            .collect()
+    }
+    /// deal one Tree, with every walker Edge and one of everyone else's
+    fn sample(&self) -> Tree {
+        let mut tree = Tree::empty(self.walker);
+        let root = &tree.insert(self.sampler.root());
+        let mut todo = self.explore(root);
+        while let Some(branch) = todo.pop() {
+            let node = &tree.attach(branch);
+            todo.extend(self.explore(node));
+        }
+        tree
+    }
+    fn explore(&self, node: &Node) -> Vec<Branch> {
+        let mut branches = self.sampler.branches(node);
+        let player = node.player();
+        if branches.len() <= 1 || player == self.walker || player == Player::chance() {
+            return branches;
+        }
+        let weights = branches
+            .iter()
+            .map(|branch| self.weight(node.bucket(), branch.edge()))
+            .collect::<Vec<Probability>>();
+        let choice = WeightedIndex::new(&weights)
+            .or_else(|_| WeightedIndex::new(vec![1.; weights.len()]))
+            .expect("at least one edge")
+            .sample(&mut rand::thread_rng());
+        vec![branches.remove(choice)]
+    }
+    /// unnormalized opponent weight on an Edge, uniform if untrained
+    fn weight(&self, bucket: &Bucket, edge: &Edge) -> Probability {
+        match self.profile.lookup(bucket) {
+            None => 1.,
+            Some(policy) => policy.inner().get(edge).copied().unwrap_or(0.),
+        }
+    }
+    /// what a Node is worth to the walker under our response,
+    /// noting what every walker Edge below it was worth along the way
+    fn evaluate(&self, node: &Node, samples: &mut Vec<Sample>) -> Utility {
+        let children = node.children();
+        if children.is_empty() {
+            return node.payoff(&self.walker);
+        }
+        let values = children
+            .iter()
+            .map(|child| (*child.incoming().unwrap(), self.evaluate(child, samples)))
+            .collect::<BTreeMap<Edge, Utility>>();
+        if node.player() != self.walker {
+            return values.values().sum::<Utility>() / values.len() as Utility;
+        }
+        let bucket = *node.bucket();
+        samples.extend(values.iter().map(|(edge, value)| (bucket, *edge, *value)));
+        match self.choice(&bucket).and_then(|edge| values.get(edge)) {
+            Some(value) => *value,
+            None => values.values().sum::<Utility>() / values.len() as Utility,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::hand::Hand;
+    use crate::cards::observation::Observation;
+    use crate::clustering::abstraction::Abstraction;
+    use crate::gameplay::action::Action;
+    use crate::gameplay::game::Game;
+    use crate::gameplay::ply::Ply;
+    use crate::mccfr::data::Data;
+
+    /// the dealer folds or bets, then the big blind always folds
+    fn folding(game: Game) -> Tree {
+        let data = |game: Game| {
+            let hole = Hand::from(game.seats()[0].cards());
+            let info = Abstraction::from(Observation::from((hole, Hand::empty())));
+            Data::from((game, info))
+        };
+        let mut tree = Tree::empty(Player(Ply::Choice(0)));
+        let root = tree.insert(data(game));
+        let edges = root.continuations();
+        let root = root.index();
+        for edge in edges.into_iter().filter(|e| *e != Edge::Call) {
+            let node = tree.at(root);
+            let next = node.data().game().apply(node.actionization(&edge));
+            let head = tree.attach(Branch(data(next), edge, root)).index();
+            if edge != Edge::Fold {
+                let fold = next.apply(Action::Fold);
+                tree.attach(Branch(data(fold), Edge::Fold, head));
+            }
+        }
+        tree
+    }
+
+    #[test]
+    fn responds_to_folding() {
+        let profile = Profile::default();
+        let sampler = Sampler::default();
+        let mut response = SampledResponse::from((&profile, &sampler, Player(Ply::Choice(0))));
+        let tree = folding(Game::root());
+        let root = &tree.at(NodeIndex::new(0));
+        let mut samples = Vec::new();
+        let uniform = response.evaluate(root, &mut samples);
+        response.record(samples);
+        let value = response.evaluate(root, &mut Vec::new());
+        assert!(uniform < value);
+        assert!(value == crate::B_BLIND as Utility);
+        assert!(response.choice(root.bucket()) != Some(&Edge::Fold));
+    }
+}
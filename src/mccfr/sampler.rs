@@ -1,17 +1,30 @@
 use super::bucket::Bucket;
 use super::data::Data;
+use super::edge::Edge;
 use super::node::Node;
+use super::odds::Odds;
+use super::player::Player;
 use super::spot::Spot;
 use super::tree::Branch;
 use super::tree::Tree;
+use crate::cards::hand::Hand;
+use crate::cards::hole::Hole;
 use crate::cards::observation::Observation;
 use crate::clustering::abstraction::Abstraction;
 use crate::clustering::encoding::Encoder;
+use crate::gameplay::action::Action;
 use crate::gameplay::game::Game;
+use crate::Probability;
 
 #[derive(Default)]
 pub struct Sampler(Encoder);
 
+impl From<Encoder> for Sampler {
+    fn from(encoder: Encoder) -> Self {
+        Self(encoder)
+    }
+}
+
 impl Sampler {
     pub fn load() -> Self {
         Self(Encoder::load())
@@ -24,11 +37,75 @@ impl Sampler {
     pub fn abstraction(&self, game: &Game) -> Abstraction {
         self.0.abstraction(&Observation::from(game))
     }
-    pub fn replay(&self, _: &Spot) -> Tree {
-        todo!()
+    /// walk the abstract tree along a live Spot, one Edge per Action.
+    /// the abstract Game is dealt at training depth, so a live raise
+    /// is matched by its size relative to the pot rather than in chips.
+    /// every Node is abstracted from our own hole cards, since those
+    /// are the only ones we can see. None once the live hand goes
+    /// somewhere the abstract tree doesn't, e.g. past MAX_N_BETS raises.
+    pub fn replay(&self, spot: &Spot) -> Option<Tree> {
+        let mut seats = [(crate::STACK, Hole::empty()); crate::N];
+        for (seat, live) in seats.iter_mut().zip(spot.root().seats()) {
+            seat.1 = live.cards();
+        }
+        let blinds = (crate::S_BLIND, crate::B_BLIND);
+        let root = Game::seated(seats, spot.root().dealer(), blinds);
+        let mut live = *spot.root();
+        let mut tree = Tree::empty(Player(spot.game().player()));
+        let mut head = tree.insert(self.perceive(root, spot.hole())).index();
+        for action in spot.past() {
+            let node = tree.at(head);
+            let edge = Self::translate(&node, &live, action)?;
+            let game = match action {
+                Action::Draw(_) => node.data().game().apply(*action),
+                _ => node.data().game().apply(node.actionization(&edge)),
+            };
+            live = live.apply(*action);
+            if game.player() != live.player() {
+                return None;
+            }
+            let data = self.perceive(game, spot.hole());
+            head = tree.attach(Branch(data, edge, head)).index();
+        }
+        Some(tree)
+    }
+    /// the Bucket we would have trained this Spot under
+    pub fn bucket(&self, spot: &Spot) -> Option<Bucket> {
+        let tree = self.replay(spot)?;
+        let node = tree.all().pop()?;
+        Some(*node.bucket())
+    }
+    /// the Abstraction of these hole cards on this board
+    pub fn perceived(&self, hole: Hole, board: Hand) -> Abstraction {
+        self.0
+            .abstraction(&Observation::from((Hand::from(hole), board)))
+    }
+    /// Data for a Node of the replayed tree, as we see it
+    fn perceive(&self, game: Game, hole: Hole) -> Data {
+        let info = self.perceived(hole, Hand::from(game.board()));
+        Data::from((game, info))
     }
-    pub fn bucket(&self, _: &Spot) -> Bucket {
-        todo!();
+    /// the Edge out of an abstract Node that best stands for a live Action.
+    /// raises go to whichever Odds is nearest to the live bet over the pot.
+    fn translate(node: &Node, live: &Game, action: &Action) -> Option<Edge> {
+        let choices = node.continuations();
+        let edge = match action {
+            Action::Blind(_) => return None,
+            Action::Raise(chips) => {
+                let odds = *chips as Probability / live.pot() as Probability;
+                let distance = |o: &Odds| (Probability::from(*o) - odds).abs();
+                choices
+                    .iter()
+                    .filter_map(|edge| match edge {
+                        Edge::Raise(o) => Some(*o),
+                        _ => None,
+                    })
+                    .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+                    .map(Edge::Raise)?
+            }
+            action => Edge::from(*action),
+        };
+        choices.contains(&edge).then_some(edge)
     }
 
     /// unfiltered set of possible children of a Node,
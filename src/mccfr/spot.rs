@@ -1,20 +1,66 @@
-#![allow(unused)]
-
+use super::edge::Edge;
 use super::policy::Policy;
 use crate::cards::hole::Hole;
 use crate::gameplay::action::Action;
 use crate::gameplay::game::Game;
+use crate::Probability;
+use std::collections::BTreeMap;
 
+/// a live decision point, as seen by the seat about to act:
+/// the hand as it was dealt (blinds already posted), every Action
+/// since then, and our own hole cards. this is what we bring
+/// to the blueprint when asking it how to play.
 pub struct Spot {
     root: Game, // only used for starting stacks (hopefully)
     past: Vec<Action>,
     hole: Hole,
 }
+
+impl From<(Game, Vec<Action>)> for Spot {
+    fn from((root, past): (Game, Vec<Action>)) -> Self {
+        let game = past.iter().fold(root, |game, action| game.apply(*action));
+        Self {
+            root,
+            past,
+            hole: game.actor().cards(),
+        }
+    }
+}
+
 impl Spot {
     pub fn root(&self) -> &Game {
         &self.root
     }
+    pub fn past(&self) -> &[Action] {
+        &self.past
+    }
+    pub fn hole(&self) -> Hole {
+        self.hole
+    }
+    /// the live Game we are deciding in
+    pub fn game(&self) -> Game {
+        self.past
+            .iter()
+            .fold(self.root, |game, action| game.apply(*action))
+    }
+    /// restrict an abstract Policy to the Edges that size into
+    /// a legal Action in the live Game, and renormalize.
+    /// empty if nothing survives.
     pub fn coalesce(&self, policy: Policy) -> Policy {
-        todo!()
+        let game = self.game();
+        let legal = policy
+            .inner()
+            .iter()
+            .filter(|(edge, _)| game.validate(&Action::from((&game, **edge))).is_ok())
+            .map(|(edge, p)| (*edge, *p))
+            .collect::<BTreeMap<Edge, Probability>>();
+        let sum = legal.values().sum::<Probability>();
+        Policy::from(
+            legal
+                .into_iter()
+                .filter(|_| sum > 0.)
+                .map(|(edge, p)| (edge, p / sum))
+                .collect::<BTreeMap<Edge, Probability>>(),
+        )
     }
 }
@@ -0,0 +1,26 @@
+use super::memory::Memory;
+use super::minimizer::Minimizer;
+use crate::Probability;
+use crate::Utility;
+
+/// plain regret matching. every epoch counts the same,
+/// and accumulated regret is floored at REGRET_MIN so
+/// that an Edge can always recover from a bad start.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Vanilla;
+
+impl Minimizer for Vanilla {
+    fn add_regret(&self, _: usize, memory: &mut Memory, regret: Utility) {
+        let regret = memory.regret() + regret;
+        memory.set_regret(regret.clamp(crate::REGRET_MIN, crate::REGRET_MAX));
+    }
+    fn add_policy(&self, _: usize, memory: &mut Memory, policy: Probability) {
+        memory.add_policy(1., policy);
+    }
+}
+
+impl std::fmt::Display for Vanilla {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "vanilla")
+    }
+}
@@ -0,0 +1,17 @@
+use crate::gameplay::action::Action;
+use crate::gameplay::game::Game;
+use crate::history::hand::Play;
+
+/// a Player that may take its time deciding.
+///
+/// decisions are futures on the tokio runtime, so they can wait on a
+/// network or a slow bot without holding anything up, and they can be
+/// abandoned. seat an Agent at a Table by putting it on a Clock.
+#[async_trait::async_trait]
+pub trait Agent: Send {
+    async fn decide(&mut self, game: &Game) -> Action;
+    /// a new hand was dealt. its blinds are observed right after
+    fn begin(&mut self, _: &Game) {}
+    /// somebody just acted, or a card just fell. blinds included.
+    fn observe(&mut self, _: &Play) {}
+}
@@ -0,0 +1,56 @@
+use super::agent::Agent;
+use super::player::Player;
+use crate::gameplay::action::Action;
+use crate::gameplay::game::Game;
+use crate::history::hand::Play;
+
+/// a synchronous Player as an Agent, deciding on a blocking thread.
+///
+/// a Player can't be interrupted, so if a Clock gives up on it
+/// mid-decision it is left behind on that thread, and from then
+/// on we check when it's free and fold otherwise in its place.
+pub struct Blocking(Option<Box<dyn Player + Send>>);
+
+impl From<Box<dyn Player + Send>> for Blocking {
+    fn from(player: Box<dyn Player + Send>) -> Self {
+        Self(Some(player))
+    }
+}
+
+impl Blocking {
+    /// has it hung, or panicked, and been left behind?
+    pub fn is_gone(&self) -> bool {
+        self.0.is_none()
+    }
+}
+
+#[async_trait::async_trait]
+impl Agent for Blocking {
+    async fn decide(&mut self, game: &Game) -> Action {
+        let game = *game;
+        let Some(mut player) = self.0.take() else {
+            return game.passive();
+        };
+        let decision = tokio::task::spawn_blocking(move || {
+            let action = player.decide(&game);
+            (player, action)
+        });
+        match decision.await {
+            Ok((player, action)) => {
+                self.0 = Some(player);
+                action
+            }
+            Err(_) => game.passive(),
+        }
+    }
+    fn begin(&mut self, game: &Game) {
+        if let Some(player) = self.0.as_mut() {
+            player.begin(game);
+        }
+    }
+    fn observe(&mut self, play: &Play) {
+        if let Some(player) = self.0.as_mut() {
+            player.observe(play);
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use super::player::Player;
+use crate::gameplay::action::Action;
+use crate::gameplay::game::Game;
+
+/// always checks or calls, all-in if that's what it takes.
+/// never folds and never raises: a calling station.
+#[derive(Debug, Default)]
+pub struct Caller;
+
+impl Player for Caller {
+    fn decide(&mut self, game: &Game) -> Action {
+        match game.to_call() {
+            0 => Action::Check,
+            chips if chips >= game.to_shove() => Action::Shove(game.to_shove()),
+            chips => Action::Call(chips),
+        }
+    }
+}
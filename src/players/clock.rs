@@ -0,0 +1,150 @@
+use super::agent::Agent;
+use super::player::Player;
+use crate::gameplay::action::Action;
+use crate::gameplay::game::Game;
+use crate::history::hand::Play;
+use std::time::Duration;
+use tokio::runtime::Handle;
+use tokio::time::Instant;
+
+/// an Agent on the clock, seated at a Table like any other Player.
+///
+/// every decision gets a fixed allowance, and anything past that is
+/// drawn from a time bank that lasts the whole session. an Agent that
+/// runs out of both, or answers with something illegal, checks when
+/// it's free and folds otherwise, so a slow or vanished seat can never
+/// stall a match. decisions run on the runtime that was current when
+/// the Clock was made, and the Table has to be off that runtime's
+/// worker threads, e.g. under spawn_blocking.
+pub struct Clock<A: Agent> {
+    agent: A,
+    limit: Duration,
+    bank: Duration,
+    timeouts: usize,
+    runtime: Handle,
+}
+
+impl<A: Agent> From<(A, Duration, Duration)> for Clock<A> {
+    fn from((agent, limit, bank): (A, Duration, Duration)) -> Self {
+        Self {
+            agent,
+            limit,
+            bank,
+            timeouts: 0,
+            runtime: Handle::current(),
+        }
+    }
+}
+
+impl<A: Agent> Clock<A> {
+    pub fn agent(&self) -> &A {
+        &self.agent
+    }
+    /// time left in the bank
+    pub fn bank(&self) -> Duration {
+        self.bank
+    }
+    /// how many decisions ran out of time
+    pub fn timeouts(&self) -> usize {
+        self.timeouts
+    }
+    /// one decision against the clock, drawing on the bank past the limit
+    async fn timed(&mut self, game: &Game) -> Action {
+        let allowed = self.limit + self.bank;
+        let start = Instant::now();
+        let decision = tokio::time::timeout(allowed, self.agent.decide(game)).await;
+        let over = start.elapsed().saturating_sub(self.limit);
+        self.bank = self.bank.saturating_sub(over);
+        match decision {
+            Ok(action) => match game.validate(&action) {
+                Ok(()) => action,
+                Err(reason) => {
+                    log::warn!("illegal {} on the clock: {}", action, reason);
+                    game.passive()
+                }
+            },
+            Err(_) => {
+                log::warn!("out of time after {:?}", allowed);
+                self.timeouts += 1;
+                self.bank = Duration::ZERO;
+                game.passive()
+            }
+        }
+    }
+}
+
+impl<A: Agent> Player for Clock<A> {
+    fn decide(&mut self, game: &Game) -> Action {
+        let runtime = self.runtime.clone();
+        runtime.block_on(self.timed(game))
+    }
+    fn begin(&mut self, game: &Game) {
+        self.agent.begin(game);
+    }
+    fn observe(&mut self, play: &Play) {
+        self.agent.observe(play);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::players::blocking::Blocking;
+
+    struct Sleeper(Duration);
+
+    #[async_trait::async_trait]
+    impl Agent for Sleeper {
+        async fn decide(&mut self, game: &Game) -> Action {
+            tokio::time::sleep(self.0).await;
+            Action::Call(game.to_call())
+        }
+    }
+
+    fn clocked<A: Agent>(agent: A, bank: u64) -> Clock<A> {
+        let limit = Duration::from_millis(100);
+        Clock::from((agent, limit, Duration::from_millis(bank)))
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn bank_runs_out() {
+        let game = Game::root();
+        let mut clock = clocked(Sleeper(Duration::from_millis(200)), 150);
+        assert!(clock.timed(&game).await == Action::Call(game.to_call()));
+        assert!(clock.bank() == Duration::from_millis(50));
+        assert!(clock.timed(&game).await == Action::Fold);
+        assert!(clock.bank() == Duration::ZERO);
+        assert!(clock.timeouts() == 1);
+    }
+
+    /// a hung Player blocks a thread, which keeps tokio from moving
+    /// time on its own, so we move it ourselves until the Clock gives up.
+    /// it's left behind by then, so the next decision doesn't wait.
+    #[tokio::test(start_paused = true)]
+    async fn hung_players_are_left_behind() {
+        let game = Game::root();
+        let (_release, hung) = std::sync::mpsc::channel::<()>();
+        let hang = move |game: &Game| {
+            hung.recv().ok();
+            Action::Call(game.to_call())
+        };
+        let agent = Blocking::from(Box::new(hang) as Box<dyn Player + Send>);
+        let mut clock = clocked(agent, 0);
+        {
+            let decision = clock.timed(&game);
+            tokio::pin!(decision);
+            let action = loop {
+                tokio::select! {
+                    action = &mut decision => break action,
+                    _ = tokio::time::advance(Duration::from_millis(10)) => {}
+                }
+            };
+            assert!(action == Action::Fold);
+        }
+        assert!(clock.agent().is_gone());
+        let start = Instant::now();
+        assert!(clock.timed(&game.apply(Action::Call(1))).await == Action::Check);
+        assert!(start.elapsed() == Duration::ZERO);
+        assert!(clock.timeouts() == 1);
+    }
+}
@@ -1,105 +1,193 @@
-#![allow(dead_code)]
+use super::player::Player;
 use crate::gameplay::action::Action;
 use crate::gameplay::game::Game;
+use crate::gameplay::ply::Ply;
+use crate::history::hand::Play;
 use crate::Chips;
 use dialoguer::Input;
 use dialoguer::Select;
 
-#[derive(Debug)]
-pub struct Human;
-
-impl Human {
-    pub fn decide(game: &Game) -> Action {
-        Self::random(game)
-        // let ref choices = Self::available(game);
-        // let choice = Self::selection(choices, game);
-        // Self::choose(choices, choice, game)
-    }
-
-    fn random(game: &Game) -> Action {
-        use rand::seq::SliceRandom;
-        let ref mut rng = rand::thread_rng();
-        game.legal()
-            .choose(rng)
-            .copied()
-            .expect("decision node has options")
-    }
+/// a person at the terminal.
+///
+/// we remember every Play of the current hand so that we can show
+/// the whole table before each decision, and offer the legal actions
+/// with the usual pot-relative bet sizes alongside a custom amount.
+#[derive(Debug, Default)]
+pub struct Human {
+    plays: Vec<Play>,
+}
 
-    fn raise(game: &Game) -> Chips {
-        Input::new()
-            .with_prompt(Self::infoset(game))
-            .validate_with(|i: &String| -> Result<(), &str> {
-                let input = match i.parse::<Chips>() {
-                    Ok(value) => value,
-                    Err(_) => return Err("Enter a positive integer"),
-                };
-                if input < game.to_raise() {
-                    return Err("Raise too small");
-                }
-                if input > game.to_shove() {
-                    return Err("Raise too large");
-                }
-                Ok(())
-            })
+impl Player for Human {
+    fn decide(&mut self, game: &Game) -> Action {
+        let options = Self::options(game);
+        let labels = options
+            .iter()
+            .map(|(label, _)| label.as_str())
+            .collect::<Vec<&str>>();
+        let choice = Select::new()
+            .with_prompt(self.render(game))
             .report(false)
+            .items(&labels)
+            .default(0)
             .interact()
-            .unwrap()
-            .parse::<Chips>()
-            .unwrap()
+            .unwrap();
+        match options[choice].1 {
+            Some(action) => action,
+            None => Self::custom(game),
+        }
     }
-
-    fn infoset(game: &Game) -> String {
-        format!(
-            "\nBOARD      {}\nCARDS      {}\nPOT        {}\nSTACK      {}\nTO CALL    {}\nMIN RAISE  {}\n\nAction",
-            game.board(),
-            game.actor().cards(),
-            game.pot(),
-            game.actor().stack(),
-            game.to_call(),
-            game.to_raise(),
-        )
+    fn begin(&mut self, _: &Game) {
+        self.plays.clear();
+    }
+    fn observe(&mut self, play: &Play) {
+        self.plays.push(*play);
     }
+}
 
-    fn available(game: &Game) -> Vec<&str> {
-        game.legal()
-            .iter()
-            .map(|a| match a {
-                Action::Fold => "Fold",
-                Action::Check => "Check",
-                Action::Call(_) => "Call",
-                Action::Raise(_) => "Raise",
-                Action::Shove(_) => "Shove",
-                _ => unreachable!(),
-            })
-            .collect::<Vec<&str>>()
+impl Human {
+    /// pot-relative raise sizes, as fractions of the pot after calling
+    const PRESETS: [(&'static str, Chips, Chips); 3] =
+        [("1/3 pot", 1, 3), ("1/2 pot", 1, 2), ("pot", 1, 1)];
+
+    /// every legal option with a label. None stands for a custom raise.
+    fn options(game: &Game) -> Vec<(String, Option<Action>)> {
+        let mut options = Vec::new();
+        for action in game.legal() {
+            match action {
+                Action::Fold => options.push(("Fold".to_string(), Some(action))),
+                Action::Check => options.push(("Check".to_string(), Some(action))),
+                Action::Call(chips) => options.push((format!("Call {}", chips), Some(action))),
+                _ => {}
+            }
+        }
+        let mut sizes = Vec::new();
+        for (label, numer, denom) in Self::PRESETS {
+            let chips = game.to_call() + (game.pot() + game.to_call()) * numer / denom;
+            if chips >= game.to_raise() && chips < game.to_shove() && !sizes.contains(&chips) {
+                sizes.push(chips);
+                options.push((
+                    format!("Raise {} ({})", chips, label),
+                    Some(Action::Raise(chips)),
+                ));
+            }
+        }
+        if game.legal().iter().any(|a| matches!(a, Action::Raise(_))) {
+            options.push((
+                format!("Raise custom [{}, {}]", game.to_raise(), game.to_shove()),
+                None,
+            ));
+        }
+        if game.legal().iter().any(|a| matches!(a, Action::Shove(_))) {
+            options.push((
+                format!("All-in {}", game.to_shove()),
+                Some(Action::Shove(game.to_shove())),
+            ));
+        }
+        options
     }
 
-    fn selection(choices: &[&str], game: &Game) -> usize {
-        Select::new()
-            .with_prompt(Self::infoset(game))
+    /// ask for a raise amount until it fits between min raise and all-in
+    fn custom(game: &Game) -> Action {
+        let raise = Input::new()
+            .with_prompt(format!(
+                "Raise amount [{}, {}]",
+                game.to_raise(),
+                game.to_shove()
+            ))
+            .validate_with(|i: &String| -> Result<(), String> {
+                Self::validate(game, i).map(|_| ())
+            })
             .report(false)
-            .items(choices)
-            .default(0)
             .interact()
-            .unwrap()
+            .unwrap();
+        Self::validate(game, &raise).expect("validated input")
+    }
+
+    fn validate(game: &Game, input: &str) -> Result<Action, String> {
+        match input.trim().parse::<Chips>() {
+            Err(_) => Err("Enter a positive integer".to_string()),
+            Ok(chips) if chips < game.to_raise() => {
+                Err(format!("Raise at least {}", game.to_raise()))
+            }
+            Ok(chips) if chips > game.to_shove() => {
+                Err(format!("Raise at most {}", game.to_shove()))
+            }
+            Ok(chips) if chips == game.to_shove() => Ok(Action::Shove(chips)),
+            Ok(chips) => Ok(Action::Raise(chips)),
+        }
     }
 
-    fn choose(choices: &[&str], selection: usize, game: &Game) -> Action {
-        match choices[selection] {
-            "Fold" => Action::Fold,
-            "Check" => Action::Check,
-            "Call" => Action::Call(game.to_call()),
-            "Shove" => Action::Shove(game.to_shove()),
-            "Raise" => {
-                let raise = Self::raise(game);
-                let shove = game.to_shove();
-                if raise == shove {
-                    Action::Shove(shove)
+    /// the whole table as we are allowed to see it
+    fn render(&self, game: &Game) -> String {
+        let hero = match game.player() {
+            Ply::Choice(seat) => Some(seat),
+            _ => None,
+        };
+        let mut lines = Vec::new();
+        lines.push(String::new());
+        lines.push(format!("BOARD      {}", game.board()));
+        lines.push(format!("POT        {}", game.pot()));
+        for (i, seat) in game.seats().iter().enumerate() {
+            lines.push(format!(
+                "P{}{:<8} {:>6}  {}{}",
+                i + 1,
+                if i == game.dealer() { " (btn)" } else { "" },
+                seat.stack(),
+                seat.state(),
+                if Some(i) == hero {
+                    format!("  {}  <- you", seat.cards())
                 } else {
-                    Action::Raise(raise)
+                    String::new()
                 }
+            ));
+        }
+        let mut street = None;
+        for play in self.plays.iter() {
+            if street != Some(play.street) {
+                street = Some(play.street);
+                lines.push(format!("{}", play.street).to_uppercase());
+            }
+            match play.seat {
+                Some(seat) => lines.push(format!("  P{}  {}", seat + 1, play.action)),
+                None => lines.push(format!("      {}", play.action)),
             }
-            _ => unreachable!(),
         }
+        lines.push(format!("TO CALL    {}", game.to_call()));
+        lines.push(format!("MIN RAISE  {}", game.to_raise()));
+        lines.push(String::new());
+        lines.push("Action".to_string());
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_are_legal() {
+        let game = Game::root();
+        let options = Human::options(&game);
+        assert!(options.iter().any(|(_, a)| a.is_none()));
+        assert!(options
+            .iter()
+            .filter_map(|(_, a)| *a)
+            .all(|a| game.validate(&a).is_ok()));
+    }
+
+    #[test]
+    fn custom_bounds() {
+        let game = Game::root();
+        assert!(Human::validate(&game, "x").is_err());
+        assert!(Human::validate(&game, &(game.to_raise() - 1).to_string()).is_err());
+        assert!(Human::validate(&game, &(game.to_shove() + 1).to_string()).is_err());
+        assert!(
+            Human::validate(&game, &game.to_shove().to_string())
+                == Ok(Action::Shove(game.to_shove()))
+        );
+        assert!(
+            Human::validate(&game, &game.to_raise().to_string())
+                == Ok(Action::Raise(game.to_raise()))
+        );
     }
 }
@@ -0,0 +1,392 @@
+use super::player::Player;
+use super::robot::Robot;
+use crate::cards::card::Card;
+use crate::cards::deck::Deck;
+use crate::cards::hand::Hand;
+use crate::cards::hands::HandIterator;
+use crate::cards::hole::Hole;
+use crate::cards::strength::Strength;
+use crate::gameplay::action::Action;
+use crate::gameplay::arena::Tally;
+use crate::gameplay::game::Game;
+use crate::gameplay::game::Position;
+use crate::gameplay::table::Table;
+use crate::history::hand::HandHistory;
+use crate::history::hand::Play;
+use crate::mccfr::blueprint::Solver;
+use crate::mccfr::edge::Edge;
+use crate::mccfr::odds::Odds;
+use crate::mccfr::spot::Spot;
+use crate::Chips;
+use crate::Probability;
+use crate::Utility;
+use crate::N;
+use rand::rngs::SmallRng;
+use rayon::iter::IntoParallelIterator;
+use rayon::iter::ParallelIterator;
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+/// Local Best Response: a greedy exploiter of the blueprint,
+/// playing the real game rather than the abstract one.
+///
+/// we keep a range over the blueprint's hole cards, starting uniform
+/// and reweighted by how likely the blueprint was to take every Action
+/// we saw it take, holding each of them. at each of our own decisions,
+/// we compare checking or calling, folding, and every raise in our bet
+/// size set, assuming the hand is checked down from there on.
+/// a raise is worth whatever folds it gets, plus our equity against
+/// the range when it's called. equity is rolled out over seeded boards.
+pub struct Lbr {
+    model: Robot,
+    raises: Vec<Odds>,
+    rollouts: usize,
+    seat: Position,
+    root: Option<Game>,
+    blinds: usize,
+    past: Vec<Action>,
+    range: Vec<(Hole, Probability)>,
+}
+
+impl From<(Arc<Solver>, Vec<Odds>)> for Lbr {
+    fn from((solver, raises): (Arc<Solver>, Vec<Odds>)) -> Self {
+        Self {
+            model: Robot::from(solver),
+            raises,
+            rollouts: crate::LBR_ROLLOUTS,
+            seat: 0,
+            root: None,
+            blinds: 0,
+            past: Vec::new(),
+            range: Vec::new(),
+        }
+    }
+}
+
+impl Lbr {
+    /// pot fractions to consider raising, besides going all-in
+    pub const RAISES: [Odds; 3] = [Odds(1, 2), Odds(1, 1), Odds(2, 1)];
+
+    pub fn set_rollouts(&mut self, rollouts: usize) {
+        self.rollouts = rollouts;
+    }
+
+    /// measure the saved blueprint against LBR
+    pub fn evaluate() {
+        let solver = Arc::new(Solver::load());
+        let winrate = Self::play(solver, &Self::RAISES, crate::LBR_HANDS);
+        log::info!("{}", winrate);
+    }
+    /// play this many hands against the blueprint, switching seats
+    /// every hand, with deals spread across threads with rayon
+    pub fn play(solver: Arc<Solver>, raises: &[Odds], hands: usize) -> Winrate {
+        let tally = (0..hands)
+            .into_par_iter()
+            .map(|id| {
+                let seat = id % N;
+                let lbr = Box::new(Self::from((solver.clone(), raises.to_vec())));
+                let bot = Box::new(Robot::from(solver.clone()));
+                let mut players = match seat {
+                    0 => [lbr as Box<dyn Player>, bot as Box<dyn Player>],
+                    _ => [bot as Box<dyn Player>, lbr as Box<dyn Player>],
+                };
+                let mut deck = Deck::new();
+                let seats = [(); N].map(|_| (crate::STACK, deck.hole()));
+                let blinds = (crate::S_BLIND, crate::B_BLIND);
+                let game = Game::seated(seats, 0, blinds);
+                let hand = Table::deal(&mut players, &mut [], game, id, |game| {
+                    deck.deal(game.board().street())
+                });
+                Tally::from(hand.settlements()[seat].pnl())
+            })
+            .reduce(Tally::default, |a, b| a + b);
+        Winrate {
+            bblind: crate::B_BLIND,
+            tally,
+        }
+    }
+
+    fn opponent(&self) -> Position {
+        (self.seat + 1) % N
+    }
+    /// the Spot the blueprint would be in after these Actions,
+    /// were it holding these cards
+    fn spot(&self, hole: Hole, past: Vec<Action>) -> Spot {
+        let root = self.root.expect("hand has begun");
+        Spot::from((root.holding(self.opponent(), hole), past))
+    }
+    /// how likely the blueprint was to take an Action, holding these cards
+    fn likelihood(&self, hole: Hole, action: &Action) -> Probability {
+        self.model
+            .distribution(&self.spot(hole, self.past.clone()))
+            .into_iter()
+            .filter(|(a, _)| a == action)
+            .map(|(_, p)| p)
+            .sum()
+    }
+    /// Bayes' rule over the range, once the blueprint has acted.
+    /// if the model couldn't have done what it did, we've lost
+    /// track of it, and start over from a uniform range.
+    fn update(&mut self, action: &Action) {
+        let posterior = self
+            .range
+            .iter()
+            .map(|(hole, p)| (*hole, p * self.likelihood(*hole, action)))
+            .collect::<Vec<_>>();
+        if posterior.iter().any(|(_, p)| *p > 0.) {
+            self.range = posterior;
+        } else {
+            self.range.iter_mut().for_each(|(_, p)| *p = 1.);
+        }
+    }
+    /// how often the range folds to us making this Action
+    fn folds(&self, action: Action) -> Probability {
+        let past = self.past.iter().copied().chain(std::iter::once(action));
+        let past = past.collect::<Vec<Action>>();
+        let (folds, total) = self
+            .range
+            .iter()
+            .filter(|(_, p)| *p > 0.)
+            .map(|(hole, p)| {
+                let fold = self
+                    .model
+                    .distribution(&self.spot(*hole, past.clone()))
+                    .into_iter()
+                    .filter(|(a, _)| *a == Action::Fold)
+                    .map(|(_, p)| p)
+                    .sum::<Probability>();
+                (p * fold, p)
+            })
+            .fold((0., 0.), |(f, t), (a, b)| (f + a, t + b));
+        if total > 0. {
+            folds / total
+        } else {
+            0.
+        }
+    }
+    /// our chances of winning a showdown against the range.
+    /// we enumerate every runout when there are no more of them than
+    /// our rollouts, and otherwise deal that many, seeded by the cards
+    /// we can see so the same spot always gets the same boards.
+    fn equity(&self, game: &Game) -> Probability {
+        use rand::seq::SliceRandom;
+        use rand::SeedableRng;
+        let hole = Hand::from(game.actor().cards());
+        let board = Hand::from(game.board());
+        let dead = Hand::add(hole, board);
+        let missing = 5 - board.size();
+        let every = HandIterator::from((missing, dead));
+        let runouts: Box<dyn Iterator<Item = Hand>> = if missing == 0 {
+            Box::new(std::iter::once(Hand::empty()))
+        } else if every.combinations() <= self.rollouts {
+            Box::new(every)
+        } else {
+            let deck = Vec::<Card>::from(dead.complement());
+            let mut rng = SmallRng::seed_from_u64(u64::from(dead));
+            Box::new((0..self.rollouts).map(move |_| {
+                Hand::from(
+                    deck.choose_multiple(&mut rng, missing)
+                        .copied()
+                        .collect::<Vec<Card>>(),
+                )
+            }))
+        };
+        let (mut won, mut total) = (0., 0.);
+        for runout in runouts {
+            let runout = Hand::add(board, runout);
+            let ours = Strength::from(Hand::add(hole, runout));
+            for (theirs, p) in self.range.iter() {
+                let theirs = Hand::from(*theirs);
+                if u64::from(theirs) & u64::from(runout) != 0 {
+                    continue;
+                }
+                won += p * match ours.cmp(&Strength::from(Hand::add(theirs, runout))) {
+                    Ordering::Greater => 1.,
+                    Ordering::Equal => 0.5,
+                    Ordering::Less => 0.,
+                };
+                total += p;
+            }
+        }
+        if total > 0. {
+            won / total
+        } else {
+            0.5
+        }
+    }
+    /// what an Action is worth, relative to folding,
+    /// if the hand is checked down once it's called
+    fn utility(&self, game: &Game, equity: Probability, action: Action) -> Utility {
+        let pot = game.pot() as Utility;
+        let call = game.to_call() as Utility;
+        match action {
+            Action::Fold => 0.,
+            Action::Check | Action::Call(_) => equity * (pot + call) - call,
+            Action::Shove(chips) if chips <= game.to_call() => equity * (pot + call) - call,
+            Action::Raise(chips) | Action::Shove(chips) => {
+                let bet = chips as Utility;
+                let folds = self.folds(action);
+                let called = equity * (pot + bet + bet - call) - bet;
+                folds * pot + (1. - folds) * called
+            }
+            _ => unreachable!("only choices are valued"),
+        }
+    }
+    /// every Action we'd consider here, legal and without repeats
+    fn choices(&self, game: &Game) -> Vec<Action> {
+        let mut choices = vec![game.passive(), Action::from((game, Edge::Call))];
+        choices.extend(
+            self.raises
+                .iter()
+                .map(|o| Action::from((game, Edge::Raise(*o)))),
+        );
+        choices.push(Action::Shove(game.to_shove()));
+        choices.retain(|action| game.validate(action).is_ok());
+        choices.sort();
+        choices.dedup();
+        choices
+    }
+}
+
+impl Player for Lbr {
+    fn decide(&mut self, game: &Game) -> Action {
+        let equity = self.equity(game);
+        self.choices(game)
+            .into_iter()
+            .map(|action| (action, self.utility(game, equity, action)))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(action, _)| action)
+            .unwrap_or_else(|| game.passive())
+    }
+    fn begin(&mut self, game: &Game) {
+        self.seat = game
+            .seats()
+            .iter()
+            .position(|seat| seat.cards() != Hole::empty())
+            .expect("we can see our own cards");
+        let dead = Hand::from(game.seats()[self.seat].cards());
+        self.range = HandIterator::from((2, dead))
+            .map(|hand| (Hole::from(hand), 1.))
+            .collect();
+        self.root = Some(*game);
+        self.blinds = HandHistory::from((0, game)).posted().len();
+        self.past.clear();
+    }
+    fn observe(&mut self, play: &Play) {
+        if self.blinds > 0 {
+            self.blinds -= 1;
+            return;
+        }
+        match (play.seat, play.action) {
+            (None, Action::Draw(cards)) => {
+                let cards = u64::from(cards);
+                self.range
+                    .retain(|(hole, _)| u64::from(Hand::from(*hole)) & cards == 0);
+            }
+            (Some(seat), action) if seat == self.opponent() => self.update(&action),
+            _ => {}
+        }
+        self.past.push(play.action);
+    }
+}
+
+/// what LBR won off the blueprint
+pub struct Winrate {
+    bblind: Chips,
+    tally: Tally,
+}
+
+impl Winrate {
+    pub fn hands(&self) -> usize {
+        self.tally.n()
+    }
+    /// milli-big-blinds won per hand, and the half-width
+    /// of its 95% confidence interval
+    pub fn mbb(&self) -> (f64, f64) {
+        let scale = 1000. / self.bblind as f64;
+        let mean = self.tally.mean() * scale;
+        let interval = 1.96 * self.tally.error() * scale;
+        (mean, interval)
+    }
+}
+
+impl std::fmt::Display for Winrate {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let (mean, interval) = self.mbb();
+        write!(
+            f,
+            "LBR over {} hands {:>+9.2} ± {:.2} mbb/hand (95%)",
+            self.hands(),
+            mean,
+            interval
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::street::Street;
+
+    /// seat 0 holding these cards, facing a calling station
+    fn dealt(hole: &str) -> (Lbr, Game) {
+        let seats = [
+            (crate::STACK, Hole::try_from(hole).unwrap()),
+            (crate::STACK, Hole::try_from("Kc Kd").unwrap()),
+        ];
+        let game = Game::seated(seats, 0, (crate::S_BLIND, crate::B_BLIND));
+        let mut lbr = Lbr::from((Arc::new(Solver::default()), Lbr::RAISES.to_vec()));
+        lbr.begin(&game.seen_by(0));
+        for play in HandHistory::from((0, &game)).posted() {
+            lbr.observe(play);
+        }
+        (lbr, game)
+    }
+
+    #[test]
+    fn shoves_aces_into_a_station() {
+        let (mut lbr, game) = dealt("As Ah");
+        assert!(lbr.range.len() == 1225);
+        let action = lbr.decide(&game.seen_by(0));
+        assert!(action == Action::Shove(game.to_shove()));
+    }
+
+    #[test]
+    fn calls_trash_into_a_station() {
+        let (mut lbr, game) = dealt("7c 2d");
+        lbr.set_rollouts(1024);
+        let action = lbr.decide(&game.seen_by(0));
+        assert!(action == Action::Call(game.to_call()));
+    }
+
+    #[test]
+    fn narrows_range() {
+        let (mut lbr, game) = dealt("As Ah");
+        let plays = [
+            (Some(0), Action::Call(game.to_call())),
+            (Some(1), Action::Check),
+            (None, Action::Draw(Hand::try_from("2c 3c 4c").unwrap())),
+        ];
+        for (seat, action) in plays {
+            lbr.observe(&Play {
+                street: Street::Pref,
+                seat,
+                action,
+            });
+        }
+        assert!(lbr.range.len() == 1081);
+        assert!(lbr.range.iter().all(|(_, p)| *p == 1.));
+    }
+
+    #[test]
+    fn winrate_interval() {
+        let tally = [2, -2, 2, -2]
+            .map(Tally::from)
+            .into_iter()
+            .fold(Tally::default(), |a, b| a + b);
+        let winrate = Winrate { bblind: 2, tally };
+        let (mean, interval) = winrate.mbb();
+        assert!(mean == 0.);
+        assert!((interval - 1.96 * (4f64 / 3.).sqrt() * 500.).abs() < 1e-9);
+    }
+}
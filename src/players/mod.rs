@@ -1 +1,12 @@
+pub mod agent;
+pub mod blocking;
+pub mod caller;
+pub mod clock;
 pub mod human;
+pub mod lbr;
+pub mod player;
+pub mod raiser;
+pub mod random;
+pub mod robot;
+pub mod tag;
+pub mod threshold;
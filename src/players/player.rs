@@ -0,0 +1,26 @@
+use crate::gameplay::action::Action;
+use crate::gameplay::game::Game;
+use crate::history::hand::Play;
+
+/// anything that can sit at a Table and make decisions.
+///
+/// the Game a Player sees is the public state of the hand plus
+/// its own hole cards. every other seat's cards are hidden.
+/// the returned Action must be one the Game considers legal.
+pub trait Player {
+    fn decide(&mut self, game: &Game) -> Action;
+    /// a new hand was dealt. its blinds are observed right after
+    fn begin(&mut self, _: &Game) {}
+    /// somebody just acted, or a card just fell. blinds included.
+    fn observe(&mut self, _: &Play) {}
+}
+
+/// any closure over the Game will do, which is handy for scripted play
+impl<F> Player for F
+where
+    F: FnMut(&Game) -> Action,
+{
+    fn decide(&mut self, game: &Game) -> Action {
+        self(game)
+    }
+}
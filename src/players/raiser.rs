@@ -0,0 +1,33 @@
+use super::caller::Caller;
+use super::player::Player;
+use crate::gameplay::action::Action;
+use crate::gameplay::game::Game;
+use crate::Chips;
+
+/// always makes the smallest legal raise, shoves once raises
+/// run out, and only calls when it can't put in any more.
+#[derive(Debug, Default)]
+pub struct Raiser;
+
+impl Raiser {
+    /// put in about this many chips, snapped into the legal range.
+    /// anything at or beyond our stack is a shove, and if raising
+    /// is off the table we just call.
+    pub fn bet(game: &Game, chips: Chips) -> Action {
+        let legal = game.legal();
+        let chips = chips.max(game.to_raise());
+        if chips < game.to_shove() && legal.iter().any(|a| matches!(a, Action::Raise(_))) {
+            Action::Raise(chips)
+        } else if legal.iter().any(|a| matches!(a, Action::Shove(_))) {
+            Action::Shove(game.to_shove())
+        } else {
+            Caller.decide(game)
+        }
+    }
+}
+
+impl Player for Raiser {
+    fn decide(&mut self, game: &Game) -> Action {
+        Self::bet(game, game.to_raise())
+    }
+}
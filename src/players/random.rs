@@ -0,0 +1,18 @@
+use super::player::Player;
+use crate::gameplay::action::Action;
+use crate::gameplay::game::Game;
+
+/// picks uniformly among the legal options
+#[derive(Debug, Default)]
+pub struct Random;
+
+impl Player for Random {
+    fn decide(&mut self, game: &Game) -> Action {
+        use rand::seq::SliceRandom;
+        let rng = &mut rand::thread_rng();
+        game.legal()
+            .choose(rng)
+            .copied()
+            .expect("decision node has options")
+    }
+}
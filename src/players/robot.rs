@@ -0,0 +1,148 @@
+use super::caller::Caller;
+use super::player::Player;
+use crate::gameplay::action::Action;
+use crate::gameplay::game::Game;
+use crate::history::hand::HandHistory;
+use crate::history::hand::Play;
+use crate::mccfr::blueprint::Solver;
+use crate::mccfr::edge::Edge;
+use crate::mccfr::policy::Policy;
+use crate::mccfr::spot::Spot;
+use crate::Probability;
+use std::sync::Arc;
+
+/// plays the trained blueprint.
+///
+/// every decision replays the hand so far through the abstract tree
+/// to find its Bucket, samples an Edge from the Profile policy there,
+/// and sizes it against the live Game just like training does.
+///
+/// when the blueprint has no advice, because the hand left the abstract
+/// tree or landed in a Bucket training never visited, we check if it's
+/// free and call otherwise. that never folds a hand we could see for
+/// free, and never puts in more than we were asked to.
+pub struct Robot {
+    solver: Arc<Solver>,
+    root: Option<Game>,
+    blinds: usize,
+    past: Vec<Action>,
+}
+
+impl Robot {
+    /// the blueprint and encoder saved by training
+    pub fn load() -> Self {
+        Self::from(Solver::load())
+    }
+
+    /// how likely we'd be to take each Action at a Spot, holding
+    /// its hole cards. the same odds decide() plays by, fallback included.
+    pub fn distribution(&self, spot: &Spot) -> Vec<(Action, Probability)> {
+        let game = spot.game();
+        match self.solver.advise(spot) {
+            Some(policy) => policy
+                .inner()
+                .iter()
+                .map(|(edge, p)| (Action::from((&game, *edge)), *p))
+                .collect(),
+            None => vec![(Caller.decide(&game), 1.)],
+        }
+    }
+
+    fn sample(policy: &Policy) -> Edge {
+        use rand::distributions::Distribution;
+        use rand::distributions::WeightedIndex;
+        let (edges, weights): (Vec<Edge>, Vec<Probability>) = policy.inner().iter().unzip();
+        let index = WeightedIndex::new(weights)
+            .expect("coalesced policy sums to one")
+            .sample(&mut rand::thread_rng());
+        edges[index]
+    }
+    /// the Spot we are in, if what we observed adds up to the Game we were shown
+    fn spot(&self, game: &Game) -> Option<Spot> {
+        let spot = Spot::from((self.root?, self.past.clone()));
+        let seen = spot.game();
+        (seen.pot() == game.pot() && seen.player() == game.player()).then_some(spot)
+    }
+}
+
+impl From<Solver> for Robot {
+    fn from(solver: Solver) -> Self {
+        Self::from(Arc::new(solver))
+    }
+}
+
+/// share one blueprint between many Robots, e.g. across threads
+impl From<Arc<Solver>> for Robot {
+    fn from(solver: Arc<Solver>) -> Self {
+        Self {
+            solver,
+            root: None,
+            blinds: 0,
+            past: Vec::new(),
+        }
+    }
+}
+
+impl Player for Robot {
+    fn decide(&mut self, game: &Game) -> Action {
+        match self.spot(game).and_then(|spot| self.solver.advise(&spot)) {
+            Some(policy) => Action::from((game, Self::sample(&policy))),
+            None => Caller.decide(game),
+        }
+    }
+    fn begin(&mut self, game: &Game) {
+        self.root = Some(*game);
+        self.blinds = HandHistory::from((0, game)).posted().len();
+        self.past.clear();
+    }
+    fn observe(&mut self, play: &Play) {
+        if self.blinds > 0 {
+            self.blinds -= 1;
+        } else {
+            self.past.push(play.action);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mccfr::profile::Profile;
+    use crate::mccfr::sampler::Sampler;
+
+    #[test]
+    fn unseen_bucket_calls() {
+        let game = Game::root();
+        let mut robot = Robot::from(Solver::default());
+        robot.begin(&game);
+        assert!(robot.decide(&game) == Action::Call(game.to_call()));
+    }
+
+    #[test]
+    fn follows_blueprint() {
+        let game = Game::root();
+        let sampler = Sampler::default();
+        let spot = Spot::from((game, vec![]));
+        let tree = sampler.replay(&spot).unwrap();
+        let node = tree.at(tree.all()[0].index());
+        let mut profile = Profile::default();
+        profile.witness(&node, &sampler.branches(&node));
+        let solver = Solver::from((profile, Sampler::default()));
+        assert!(solver.advise(&spot).is_some());
+        let mut robot = Robot::from(solver);
+        robot.begin(&game);
+        assert!(game.validate(&robot.decide(&game)).is_ok());
+    }
+
+    #[test]
+    fn replays_past_actions() {
+        let root = Game::root();
+        let raise = Action::Raise(root.to_raise());
+        let game = root.apply(raise);
+        let spot = Spot::from((root, vec![raise]));
+        let tree = Sampler::default().replay(&spot).unwrap();
+        let node = tree.all().pop().unwrap();
+        assert!(node.data().game().player() == game.player());
+        assert!(node.incoming().is_some_and(|edge| edge.is_raise()));
+    }
+}
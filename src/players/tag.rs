@@ -0,0 +1,102 @@
+use super::caller::Caller;
+use super::player::Player;
+use super::raiser::Raiser;
+use crate::cards::hand::Hand;
+use crate::cards::hole::Hole;
+use crate::cards::rank::Rank;
+use crate::cards::ranking::Ranking;
+use crate::cards::street::Street;
+use crate::cards::strength::Strength;
+use crate::gameplay::action::Action;
+use crate::gameplay::game::Game;
+
+/// tight-aggressive rules of thumb.
+///
+/// preflop, hole cards are sorted into a Class: premium hands raise
+/// and reraise, strong hands open and call a raise, playable hands
+/// only see a cheap flop, and the rest check or fold. after the flop
+/// it goes by made Strength: two pair or better bets the pot, a pair
+/// calls up to half the pot, and anything less checks or folds.
+#[derive(Debug, Default)]
+pub struct Tag;
+
+/// a coarse preflop ranking of starting hands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Class {
+    Trash,
+    Playable,
+    Strong,
+    Premium,
+}
+
+impl From<Hole> for Class {
+    fn from(hole: Hole) -> Self {
+        let hand = Hand::from(hole);
+        let hi = hand.max_rank().expect("two cards");
+        let lo = hand.min_rank().expect("two cards");
+        let paired = hi == lo;
+        let suits = hand.map(|c| c.suit()).collect::<Vec<_>>();
+        let suited = suits[0] == suits[1];
+        let gap = u8::from(hi) - u8::from(lo);
+        match (hi, lo) {
+            _ if paired && lo >= Rank::Ten => Class::Premium,
+            (Rank::Ace, Rank::King) => Class::Premium,
+            (Rank::Ace, Rank::Queen) if suited => Class::Premium,
+            _ if paired && lo >= Rank::Seven => Class::Strong,
+            (Rank::Ace, Rank::Queen) | (Rank::King, Rank::Queen) => Class::Strong,
+            (Rank::Ace, Rank::Jack) if suited => Class::Strong,
+            _ if paired => Class::Playable,
+            _ if lo >= Rank::Ten => Class::Playable,
+            (Rank::Ace, _) if suited => Class::Playable,
+            _ if suited && gap == 1 && lo >= Rank::Four => Class::Playable,
+            _ => Class::Trash,
+        }
+    }
+}
+
+impl Tag {
+    fn preflop(game: &Game) -> Action {
+        let blind = game.bblind();
+        let open = game.to_call() < blind;
+        match Class::from(game.actor().cards()) {
+            Class::Premium => Raiser::bet(game, game.to_call() + 3 * blind.max(game.to_call())),
+            Class::Strong if open => Raiser::bet(game, 3 * blind),
+            Class::Strong if game.to_call() <= 4 * blind => Caller.decide(game),
+            Class::Playable if game.to_call() <= blind => Caller.decide(game),
+            _ => game.passive(),
+        }
+    }
+    fn postflop(game: &Game) -> Action {
+        let hand = Hand::add(Hand::from(game.actor().cards()), Hand::from(game.board()));
+        match Strength::from(hand).ranking() {
+            Ranking::HighCard(_) => game.passive(),
+            Ranking::OnePair(_) if 2 * game.to_call() <= game.pot() => Caller.decide(game),
+            Ranking::OnePair(_) => game.passive(),
+            _ => Raiser::bet(game, game.to_call() + game.pot()),
+        }
+    }
+}
+
+impl Player for Tag {
+    fn decide(&mut self, game: &Game) -> Action {
+        match game.board().street() {
+            Street::Pref => Self::preflop(game),
+            _ => Self::postflop(game),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preflop_classes() {
+        let class = |s: &str| Class::from(Hole::try_from(s).unwrap());
+        assert!(class("As Ad") == Class::Premium);
+        assert!(class("Ks Ah") == Class::Premium);
+        assert!(class("8s 8d") == Class::Strong);
+        assert!(class("7h 6h") == Class::Playable);
+        assert!(class("7h 2c") == Class::Trash);
+    }
+}
@@ -0,0 +1,138 @@
+use super::caller::Caller;
+use super::player::Player;
+use super::raiser::Raiser;
+use crate::cards::deck::Deck;
+use crate::cards::hand::Hand;
+use crate::cards::street::Street;
+use crate::cards::strength::Strength;
+use crate::gameplay::action::Action;
+use crate::gameplay::game::Game;
+use crate::Probability;
+use std::cmp::Ordering;
+
+/// plays by showdown equity against a random hand.
+///
+/// equity is estimated by Monte Carlo, dealing the opponent and the
+/// rest of the board at random. above the raise threshold we bet the
+/// pot, above the pot odds we call, and otherwise we check or fold.
+#[derive(Debug)]
+pub struct Threshold {
+    raise: Probability,
+    samples: usize,
+}
+
+impl Default for Threshold {
+    fn default() -> Self {
+        Self::from(0.7)
+    }
+}
+
+/// raise whenever equity is at least this much
+impl From<Probability> for Threshold {
+    fn from(raise: Probability) -> Self {
+        Self {
+            raise,
+            samples: 256,
+        }
+    }
+}
+
+impl Threshold {
+    /// share of the pot we expect to win at showdown against a random hand
+    pub fn equity(&self, game: &Game) -> Probability {
+        let hole = Hand::from(game.actor().cards());
+        let board = Hand::from(game.board());
+        let known = Hand::add(hole, board);
+        let won = (0..self.samples)
+            .map(|_| {
+                let mut deck = Deck::from(known.complement());
+                let villain = Hand::from(deck.hole());
+                let mut board = board;
+                let mut street = game.board().street();
+                while street != Street::Rive {
+                    board = Hand::add(board, deck.deal(street));
+                    street = street.next();
+                }
+                let hero = Strength::from(Hand::add(hole, board));
+                let villain = Strength::from(Hand::add(villain, board));
+                match hero.cmp(&villain) {
+                    Ordering::Greater => 1.,
+                    Ordering::Equal => 0.5,
+                    Ordering::Less => 0.,
+                }
+            })
+            .sum::<Probability>();
+        won / self.samples.max(1) as Probability
+    }
+}
+
+impl Player for Threshold {
+    fn decide(&mut self, game: &Game) -> Action {
+        let equity = self.equity(game);
+        let odds = game.to_call() as Probability / (game.pot() + game.to_call()) as Probability;
+        if equity >= self.raise {
+            Raiser::bet(game, game.to_call() + game.pot())
+        } else if equity >= odds {
+            Caller.decide(game)
+        } else {
+            Action::Fold
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::hole::Hole;
+    use crate::gameplay::ply::Ply;
+    use crate::players::caller::Caller;
+    use crate::players::raiser::Raiser;
+    use crate::players::random::Random;
+    use crate::players::tag::Tag;
+
+    #[test]
+    fn aces_beat_random() {
+        let seats = [
+            (crate::STACK, Hole::try_from("As Ad").unwrap()),
+            (crate::STACK, Hole::try_from("7c 2d").unwrap()),
+        ];
+        let game = Game::seated(seats, 0, (crate::S_BLIND, crate::B_BLIND));
+        let equity = Threshold::default().equity(&game);
+        assert!(equity > 0.7);
+        assert!(equity < 0.95);
+    }
+
+    /// a Table would quietly swap an illegal action for a passive
+    /// one, so we deal hands ourselves and check every decision.
+    /// the baseline alternates seats against Random, which steers
+    /// it into spots it wouldn't reach against itself.
+    #[test]
+    fn baselines_play_legally() {
+        let baselines: Vec<(&str, Box<dyn Player>)> = vec![
+            ("caller", Box::new(Caller)),
+            ("raiser", Box::new(Raiser)),
+            ("random", Box::new(Random)),
+            ("tag", Box::new(Tag)),
+            ("threshold", Box::new(Threshold::default())),
+        ];
+        for (name, mut baseline) in baselines {
+            for hand in 0..64 {
+                let hero = hand % crate::N;
+                let mut game = Game::root();
+                loop {
+                    let action = match game.player() {
+                        Ply::Terminal => break,
+                        Ply::Chance => Action::Draw(game.draw()),
+                        Ply::Choice(seat) if seat == hero => {
+                            let action = baseline.decide(&game.seen_by(seat));
+                            assert!(game.validate(&action).is_ok(), "{} chose {}", name, action);
+                            action
+                        }
+                        Ply::Choice(seat) => Random.decide(&game.seen_by(seat)),
+                    };
+                    game = game.apply(action);
+                }
+            }
+        }
+    }
+}